@@ -0,0 +1,208 @@
+use simplex_solver::compact::CompactTableauData;
+use simplex_solver::tableau_data::TableauData;
+use simplex_solver::tableau::Tableau;
+use simplex_solver::{latex, lp, mps, report, solver};
+
+use std::io::{Read, Write};
+use std::process::exit;
+
+const USAGE: &str = "Usage: simplex [options] [FILE]
+
+Solves a linear program with the simplex method and prints every tableau on the way to the solution.
+The problem is read from FILE, or from standard input when FILE is missing or is -.
+
+Options:
+  --input FORMAT      json, lp, mps or fixed-mps. json is a TableauData object as /solve takes it and lp is the
+                      algebraic CPLEX LP format. Defaults to the extension of FILE, or json.
+  --algorithm NAME    standard or dual.
+  --big-m NAME        two-phase, big-M or numeric, for problems that need artificial variables.
+  --m-value VALUE     the value of M for --big-m numeric.
+  --entering RULE     the rule for choosing the entering variable.
+  --leaving RULE      the rule for choosing the leaving variable.
+  --arithmetic NAME   exact or float.
+  --output FORMAT     trace, json, compact, latex, markdown or html. Defaults to trace.
+  -o, --out FILE      writes the output to FILE instead of standard output.
+  --debug             prints the solver's debug output as it works.
+  -h, --help          prints this message.
+
+The exit status is 0 when the problem was solved, 1 when it could not be read or an option has a value the solver does
+not support and 2 when the solver stopped without an optimal solution, such as for an infeasible or unbounded problem.";
+
+struct Options {
+    file: Option<String>,
+    input: Option<String>,
+    algorithm: Option<String>,
+    big_m: Option<String>,
+    m_value: Option<i64>,
+    entering: Option<String>,
+    leaving: Option<String>,
+    arithmetic: Option<String>,
+    output: String,
+    out: Option<String>,
+    debug: bool,
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("simplex: {}", message);
+    exit(1);
+}
+
+fn parse_args(args: Vec<String>) -> Result<Options, String> {
+    let mut options = Options {
+        file: None,
+        input: None,
+        algorithm: None,
+        big_m: None,
+        m_value: None,
+        entering: None,
+        leaving: None,
+        arithmetic: None,
+        output: String::from("trace"),
+        out: None,
+        debug: false,
+    };
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        // Flags may be given as "--flag value" or "--flag=value".
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (String::from(flag), Some(String::from(value))),
+            _ => (arg.clone(), None),
+        };
+        let mut value = || inline_value.clone()
+                                       .or_else(|| args.next())
+                                       .ok_or_else(|| format!("{} needs a value.", flag));
+        match flag.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                exit(0);
+            },
+            "--input" => options.input = Some(value()?),
+            "--algorithm" => options.algorithm = Some(value()?),
+            "--big-m" => options.big_m = Some(value()?),
+            "--m-value" => {
+                let m_value = value()?;
+                options.m_value = Some(m_value.parse().map_err(|_| format!("'{}' is not a whole number for --m-value.", m_value))?);
+            },
+            "--entering" => options.entering = Some(value()?),
+            "--leaving" => options.leaving = Some(value()?),
+            "--arithmetic" => options.arithmetic = Some(value()?),
+            "--output" => options.output = value()?,
+            "-o" | "--out" => options.out = Some(value()?),
+            "--debug" => options.debug = true,
+            "-" => options.file = None,
+            _ if flag.starts_with('-') => return Err(format!("Unknown option '{}'. Run simplex --help for the options.", flag)),
+            _ if options.file.is_some() => return Err(String::from("Only one problem file can be given.")),
+            _ => options.file = Some(arg),
+        }
+    }
+    Ok(options)
+}
+
+fn read_problem(options: &Options) -> Result<TableauData, String> {
+    let mut text = String::new();
+    match &options.file {
+        Some(file) => text = std::fs::read_to_string(file).map_err(|error| format!("Cannot read {}: {}", file, error))?,
+        None => {
+            std::io::stdin().read_to_string(&mut text).map_err(|error| format!("Cannot read standard input: {}", error))?;
+        },
+    }
+    let extension = options.file.as_deref().and_then(|file| file.rsplit_once('.')).map(|(_, extension)| extension.to_lowercase());
+    let input = options.input.clone().or(extension).unwrap_or_else(|| String::from("json"));
+    let problem = match input.as_str() {
+        "json" => return serde_json::from_str(&text).map_err(|error| format!("The problem is not valid JSON: {}", error)),
+        "lp" => lp::read_lp(&text),
+        "mps" => mps::read_mps(&text, false),
+        "fixed-mps" => mps::read_mps(&text, true),
+        other => Err(format!("Unknown input format '{}'. Supported input formats are: json, lp, mps, fixed-mps.", other)),
+    };
+    problem.map(|problem| problem.to_tableau_data())
+}
+
+// Lines up the cells of a markdown table in columns, marking the pivot with brackets only.
+fn table(rows: &[&str]) -> Vec<String> {
+    let cells: Vec<Vec<String>> = rows.iter()
+                                      .filter(|row| !row.starts_with("|---"))
+                                      .map(|row| row.trim_matches('|').split('|').map(|cell| cell.trim().replace("**", "")).collect())
+                                      .collect();
+    let columns = cells.iter().map(|row| row.len()).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns).map(|col| cells.iter().filter_map(|row| row.get(col)).map(|cell| cell.chars().count()).max().unwrap_or(0))
+                                         .collect();
+    cells.iter()
+         .map(|row| row.iter()
+                       .zip(widths.iter())
+                       .map(|(cell, &width)| format!("{:>width$}", cell, width = width))
+                       .collect::<Vec<String>>()
+                       .join("  "))
+         .collect()
+}
+
+// The markdown report laid out for a terminal: the tableau after each pivot, followed by the solution, as the trace
+// a student would write out by hand.
+fn trace(t: &Tableau) -> String {
+    let markdown = report::tableau_to_markdown(t);
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut text: Vec<String> = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        if lines[index].starts_with('|') {
+            let end = lines[index..].iter().position(|line| !line.starts_with('|')).map_or(lines.len(), |len| index + len);
+            text.extend(table(&lines[index..end]));
+            index = end;
+        } else {
+            text.push(String::from(lines[index].trim_start_matches('#').trim_start()));
+            index += 1;
+        }
+    }
+    text.join("\n") + "\n"
+}
+
+fn main() {
+    let options = parse_args(std::env::args().skip(1).collect()).unwrap_or_else(|message| fail(&message));
+    let mut data = read_problem(&options).unwrap_or_else(|message| fail(&message));
+    if let Some(algorithm) = &options.algorithm {
+        data.solve_algorithm = algorithm.clone();
+    }
+    if let Some(big_m) = &options.big_m {
+        data.big_M_solve_algorithm = big_m.clone();
+    }
+    if let Some(m_value) = options.m_value {
+        data.m_value = m_value;
+    }
+    if let Some(entering) = &options.entering {
+        data.entering_rule = entering.clone();
+    }
+    if let Some(leaving) = &options.leaving {
+        data.leaving_rule = leaving.clone();
+    }
+    if let Some(arithmetic) = &options.arithmetic {
+        data.arithmetic = arithmetic.clone();
+    }
+    let (t, cycled) = solver::solve(data, options.debug).unwrap_or_else(|message| fail(&message));
+    let stopped = t.error;
+    let failed = t.error && !cycled;
+    let output = match options.output.as_str() {
+        "trace" => trace(&t),
+        "markdown" => report::tableau_to_markdown(&t),
+        "html" => report::tableau_to_html(&t),
+        "latex" => latex::tableau_to_latex(&t),
+        "json" => {
+            let t = if failed { Tableau::error_tableau(t) } else { t };
+            serde_json::to_string_pretty(&TableauData::new(t)).unwrap_or_else(|error| fail(&error.to_string())) + "\n"
+        },
+        "compact" => {
+            let t = if failed { Tableau::error_tableau(t) } else { t };
+            serde_json::to_string_pretty(&CompactTableauData::new(t)).unwrap_or_else(|error| fail(&error.to_string())) + "\n"
+        },
+        other => fail(&format!("Unknown output format '{}'. Supported output formats are: trace, json, compact, latex, markdown, html.", other)),
+    };
+    let written = match &options.out {
+        Some(file) => std::fs::write(file, output).map_err(|error| format!("Cannot write {}: {}", file, error)),
+        None => std::io::stdout().write_all(output.as_bytes()).map_err(|error| error.to_string()),
+    };
+    if let Err(message) = written {
+        fail(&message);
+    }
+    if stopped {
+        exit(2);
+    }
+}
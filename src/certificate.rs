@@ -0,0 +1,96 @@
+use crate::m::M;
+use crate::scalar::Scalar;
+
+use num::rational::Ratio;
+use serde::{Serialize, Deserialize};
+
+// A ray along which the objective grows without bound: every point + t * direction with t >= 0 is feasible for the
+// original problem, and the objective increases by objective_rate for each unit of t.
+#[derive(Debug, Clone)]
+pub struct UnboundedCertificate<T: Scalar = Ratio<i64>> {
+    pub entering_index: usize,
+    pub point: Vec<T>,
+    pub direction: Vec<T>,
+    pub objective_rate: M<T>,
+    pub verified: bool,
+}
+
+// Multipliers y for the rows of the original problem with yA >= 0 and yb < 0. Any feasible x >= 0 would give
+// yAx >= 0 while yAx = yb < 0, so no feasible point can exist.
+#[derive(Debug, Clone)]
+pub struct InfeasibilityCertificate<T: Scalar = Ratio<i64>> {
+    pub multipliers: Vec<T>,
+    pub verified: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UnboundedCertificateData {
+    pub entering_index: usize,
+    pub point_numerators: Vec<i64>,
+    pub point_denominators: Vec<i64>,
+    pub direction_numerators: Vec<i64>,
+    pub direction_denominators: Vec<i64>,
+    pub objective_rate_numerator: i64,
+    pub objective_rate_denominator: i64,
+    pub objective_rate_m_numerator: i64,
+    pub objective_rate_m_denominator: i64,
+    pub verified: bool,
+}
+
+impl UnboundedCertificateData {
+    pub fn new(certificate: UnboundedCertificate) -> UnboundedCertificateData {
+        let (point_num, point_den): (Vec<i64>, Vec<i64>) = certificate.point.iter()
+                                                                           .map(|el| (*el.numer(), *el.denom()))
+                                                                           .unzip();
+        let (direction_num, direction_den): (Vec<i64>, Vec<i64>) = certificate.direction.iter()
+                                                                                       .map(|el| (*el.numer(), *el.denom()))
+                                                                                       .unzip();
+        UnboundedCertificateData {
+            entering_index: certificate.entering_index,
+            point_numerators: point_num,
+            point_denominators: point_den,
+            direction_numerators: direction_num,
+            direction_denominators: direction_den,
+            objective_rate_numerator: *certificate.objective_rate.constant().numer(),
+            objective_rate_denominator: *certificate.objective_rate.constant().denom(),
+            objective_rate_m_numerator: *certificate.objective_rate.m().numer(),
+            objective_rate_m_denominator: *certificate.objective_rate.m().denom(),
+            verified: certificate.verified,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct InfeasibilityCertificateData {
+    pub multiplier_numerators: Vec<i64>,
+    pub multiplier_denominators: Vec<i64>,
+    pub verified: bool,
+}
+
+impl<T: Scalar> InfeasibilityCertificate<T> {
+    // Checks the multipliers against the original problem, allowing the given tolerance for rounding error.
+    pub fn new(multipliers: Vec<T>, a: &[Vec<T>], b: &[M<T>], tolerance: T) -> InfeasibilityCertificate<T> {
+        let n = a.first().map(|row| row.len()).unwrap_or(0);
+        let columns_nonnegative = multipliers.len() == a.len()
+                                  && (0..n).all(|col| a.iter().zip(multipliers.iter()).map(|(row, y)| row[col] * *y).sum::<T>() >= -tolerance);
+        let rhs_negative = multipliers.len() == b.len()
+                           && b.iter().zip(multipliers.iter()).map(|(b, y)| b.constant() * *y).sum::<T>() < -tolerance;
+        InfeasibilityCertificate {
+            multipliers,
+            verified: columns_nonnegative && rhs_negative,
+        }
+    }
+}
+
+impl InfeasibilityCertificateData {
+    pub fn new(certificate: InfeasibilityCertificate) -> InfeasibilityCertificateData {
+        let (multiplier_num, multiplier_den): (Vec<i64>, Vec<i64>) = certificate.multipliers.iter()
+                                                                                            .map(|el| (*el.numer(), *el.denom()))
+                                                                                            .unzip();
+        InfeasibilityCertificateData {
+            multiplier_numerators: multiplier_num,
+            multiplier_denominators: multiplier_den,
+            verified: certificate.verified,
+        }
+    }
+}
\ No newline at end of file
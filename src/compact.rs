@@ -0,0 +1,164 @@
+#![allow(non_snake_case)]
+
+use crate::tableau::{Tableau, Explanation};
+use crate::m::M;
+use crate::scalar::{Scalar, Float};
+
+use num::rational::Ratio;
+use serde::Serialize;
+
+// The response for format=compact. Every value is a single string written the way M is displayed, such as "3/4" or
+// "2M+3/4", instead of separate numerator and denominator arrays. A, b and c are the final tableau, including any
+// artificial columns and rows that setup added, so they describe the solved tableau rather than the problem that was
+// sent.
+#[derive(Debug, Serialize)]
+pub struct CompactTableauData {
+    pub A: Vec<Vec<String>>,
+    pub b: Vec<String>,
+    pub c: Vec<String>,
+    pub m: usize,
+    pub n: usize,
+    pub arithmetic: String,
+    pub error: bool,
+    pub error_message: String,
+    pub reduced_cost: Vec<String>,
+    pub basis_indecies: Vec<usize>,
+    pub obj: String,
+    // The objective in the problem's own sense, which differs from obj only for a minimization.
+    pub objective_value: String,
+    pub minimize: bool,
+    pub solved: bool,
+    pub solution: Vec<String>,
+    pub explanations: Vec<Explanation>,
+    pub alternative_solutions: Vec<Vec<String>>,
+    pub optimal_face: String,
+    pub unbounded_certificate: Option<CompactUnboundedCertificate>,
+    pub infeasibility_certificate: Option<CompactInfeasibilityCertificate>,
+    pub infeasible_rows: Vec<usize>,
+    pub degenerate_steps: Vec<usize>,
+    pub degenerate_optimal: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CompactUnboundedCertificate {
+    pub entering_index: usize,
+    pub point: Vec<String>,
+    pub direction: Vec<String>,
+    pub objective_rate: String,
+    pub verified: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CompactInfeasibilityCertificate {
+    pub multipliers: Vec<String>,
+    pub verified: bool,
+}
+
+// A value written as a fraction, or as a decimal when the problem was solved in float arithmetic, where the fractions
+// only stand in for the decimals the solver worked with.
+trait Written {
+    fn written(&self, decimal: bool) -> String;
+}
+
+impl Written for Ratio<i64> {
+    fn written(&self, decimal: bool) -> String {
+        if decimal { Float(self.to_f64()).to_string() } else { self.to_string() }
+    }
+}
+
+impl Written for M {
+    fn written(&self, decimal: bool) -> String {
+        if decimal { self.to_decimal() } else { self.to_string() }
+    }
+}
+
+fn strings<V: Written>(values: &[V], decimal: bool) -> Vec<String> {
+    values.iter().map(|el| el.written(decimal)).collect()
+}
+
+impl CompactTableauData {
+    pub fn new(t: Tableau) -> CompactTableauData {
+        let degenerate_optimal = t.solved && !t.find_degenerate_rows().is_empty();
+        let decimal = t.arithmetic.as_str() == "float";
+        let objective_value = t.objective_value().written(decimal);
+        CompactTableauData {
+            A: t.A.iter().map(|row| strings(row, decimal)).collect(),
+            b: strings(&t.b, decimal),
+            c: strings(&t.c, decimal),
+            m: t.m,
+            n: t.n,
+            arithmetic: t.arithmetic,
+            error: t.error,
+            error_message: t.error_message,
+            reduced_cost: strings(&t.reduced_cost, decimal),
+            basis_indecies: t.basis_indecies,
+            obj: t.obj.written(decimal),
+            objective_value,
+            minimize: t.minimize,
+            solved: t.solved,
+            solution: strings(&t.solution, decimal),
+            degenerate_steps: t.steps.iter()
+                                     .enumerate()
+                                     .filter(|(_, step)| step.degenerate_pivot)
+                                     .map(|(index, _)| index)
+                                     .collect(),
+            explanations: t.steps.into_iter().filter_map(|step| step.explanation).collect(),
+            alternative_solutions: t.alternative_solutions.iter().map(|solution| strings(solution, decimal)).collect(),
+            optimal_face: t.optimal_face,
+            unbounded_certificate: t.unbounded_certificate.map(|certificate| CompactUnboundedCertificate {
+                entering_index: certificate.entering_index,
+                point: strings(&certificate.point, decimal),
+                direction: strings(&certificate.direction, decimal),
+                objective_rate: certificate.objective_rate.written(decimal),
+                verified: certificate.verified,
+            }),
+            infeasibility_certificate: t.infeasibility_certificate.map(|certificate| CompactInfeasibilityCertificate {
+                multipliers: strings(&certificate.multipliers, decimal),
+                verified: certificate.verified,
+            }),
+            infeasible_rows: t.infeasible_rows,
+            degenerate_optimal,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CompactTableauData;
+    use crate::tableau::Tableau;
+    use crate::tableau_data::TableauData;
+
+    use num::rational::Ratio;
+
+    // Maximizes x1 + 2x2 subject to x1 + x2 <= 4 and x1 + x2 >= 1 with symbolic big-M, so setup adds an artificial
+    // column with the cost -M.
+    fn compact() -> CompactTableauData {
+        let r = Ratio::from_integer;
+        let a = vec![vec![r(1), r(1), r(1), r(0)], vec![r(1), r(1), r(0), r(-1)]];
+        let mut data = TableauData::from_standard_form(a, vec![r(4), r(1)], vec![r(1), r(2), r(0), r(0)]);
+        data.big_M_solve_algorithm = String::from("big-M");
+        let mut t = Tableau::new(data);
+        t.setup();
+        t.solve();
+        t.get_solution();
+        CompactTableauData::new(t)
+    }
+
+    #[test]
+    fn writes_every_value_as_a_string() {
+        let compact = compact();
+        assert!(compact.solved);
+        assert_eq!(compact.c, ["1", "2", "0", "0", "-M"]);
+        assert_eq!(compact.reduced_cost, ["1", "0", "2", "0", "M"]);
+        assert_eq!(compact.solution, ["0", "4", "0", "3", "0"]);
+        assert_eq!(compact.explanations[0].ratios, [Some(String::from("4")), Some(String::from("1"))]);
+        assert_eq!(compact.obj, "8");
+    }
+
+    #[test]
+    fn cannot_be_sent_back_as_a_problem() {
+        let compact = compact();
+        assert_eq!((compact.m, compact.n, compact.A[0].len()), (2, 5, 5));
+        assert!(serde_json::from_value::<TableauData>(serde_json::to_value(&compact).unwrap()).is_err());
+    }
+}
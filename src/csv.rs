@@ -0,0 +1,169 @@
+use crate::parse::{parse_ratio, parse_m};
+use crate::tableau_data::TableauData;
+
+use num::Zero;
+
+// A cell's position as a spreadsheet shows it, such as B3 for the second column of the third row.
+fn cell_name(row: usize, col: usize) -> String {
+    let mut letters = Vec::new();
+    let mut rest = col + 1;
+    while rest > 0 {
+        letters.push((b'A' + ((rest - 1) % 26) as u8) as char);
+        rest = (rest - 1) / 26;
+    }
+    format!("{}{}", letters.iter().rev().collect::<String>(), row + 1)
+}
+
+// Splits the text into rows of cells. Cells may be quoted, and a quoted cell can hold the delimiter, line breaks and
+// doubled quotes. Rows are numbered the way the spreadsheet numbered them, so blank rows are kept.
+fn records(text: &str, delimiter: char) -> Result<Vec<Vec<String>>, String> {
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                cell.push('"');
+            },
+            '"' if quoted => quoted = false,
+            '"' if cell.trim().is_empty() => {
+                cell.clear();
+                quoted = true;
+            },
+            _ if quoted => cell.push(ch),
+            '\r' if chars.peek() == Some(&'\n') => {},
+            '\n' | '\r' => {
+                row.push(std::mem::take(&mut cell));
+                rows.push(std::mem::take(&mut row));
+            },
+            _ if ch == delimiter => row.push(std::mem::take(&mut cell)),
+            _ => cell.push(ch),
+        }
+    }
+    if quoted {
+        return Err(format!("Row {} has a quote that is never closed.", rows.len() + 1));
+    }
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+// Reads a tableau kept in a spreadsheet. Every row is a constraint with b in its last column, except the objective row,
+// which holds c and leaves the last column empty. objective_row counts from 1 like the spreadsheet does and defaults to
+// the last row. With header set the first row names the variables. Empty cells are read as 0.
+pub fn read_csv(text: &str, objective_row: Option<usize>, header: bool, delimiter: char) -> Result<TableauData, String> {
+    let rows = records(text, delimiter)?;
+    let mut numbered: Vec<(usize, Vec<String>)> = rows.into_iter()
+                                                      .enumerate()
+                                                      .filter(|(_, cells)| cells.iter().any(|cell| !cell.trim().is_empty()))
+                                                      .collect();
+    let names: Vec<String> = if header {
+        if numbered.first().map(|(row, _)| *row) != Some(0) {
+            return Err(String::from("Row 1 should name the variables, but it is empty."));
+        }
+        numbered.remove(0).1.iter().map(|name| name.trim().to_string()).collect()
+    } else {
+        Vec::new()
+    };
+    let width = match numbered.first() {
+        Some((_, cells)) => cells.len(),
+        None => return Err(String::from("The file has no rows of values.")),
+    };
+    if width < 2 {
+        return Err(String::from("Each row needs at least one coefficient followed by b in the last column."));
+    }
+    if header && names.len() != width {
+        return Err(format!("Row 1 names {} columns, but the rows of values have {}.", names.len(), width));
+    }
+    for (row, cells) in &numbered {
+        if cells.len() != width {
+            return Err(format!("Row {} has {} cells, but row {} has {}.", row + 1, cells.len(), numbered[0].0 + 1, width));
+        }
+    }
+    let objective_row = match objective_row {
+        Some(0) => return Err(String::from("Rows are numbered from 1.")),
+        Some(row) => row - 1,
+        None => numbered.last().map(|(row, _)| *row).unwrap_or(0),
+    };
+    let objective = numbered.iter()
+                            .position(|(row, _)| *row == objective_row)
+                            .ok_or_else(|| format!("Row {} was chosen as the objective, but it has no values.", objective_row + 1))?;
+    let (_, c_cells) = numbered.remove(objective);
+    if numbered.is_empty() {
+        return Err(String::from("The file needs at least one constraint row besides the objective."));
+    }
+    let value = |cell: &str| if cell.trim().is_empty() { String::from("0") } else { cell.trim().to_string() };
+    let mut c: Vec<String> = Vec::with_capacity(width - 1);
+    for (col, cell) in c_cells[..width - 1].iter().enumerate() {
+        parse_m(&value(cell)).map_err(|message| format!("Cell {}: {}", cell_name(objective_row, col), message))?;
+        c.push(value(cell));
+    }
+    let constant = value(&c_cells[width - 1]);
+    if !parse_ratio(&constant).is_ok_and(|constant| constant.is_zero()) {
+        return Err(format!("Cell {}: The objective row has to leave the b column empty, since a constant in the objective is not supported.",
+                           cell_name(objective_row, width - 1)));
+    }
+    let mut a: Vec<Vec<String>> = Vec::with_capacity(numbered.len());
+    let mut b: Vec<String> = Vec::with_capacity(numbered.len());
+    for (row, cells) in &numbered {
+        for (col, cell) in cells.iter().enumerate() {
+            parse_ratio(&value(cell)).map_err(|message| format!("Cell {}: {}", cell_name(*row, col), message))?;
+        }
+        a.push(cells[..width - 1].iter().map(|cell| value(cell)).collect());
+        b.push(value(&cells[width - 1]));
+    }
+    let names = names.into_iter().take(width - 1).collect();
+    let mut data = TableauData::from_standard_form(Vec::new(), Vec::new(), Vec::new());
+    data.variable_names = names;
+    data.m = a.len();
+    data.n = width - 1;
+    data.A = a;
+    data.b = b;
+    data.c = c;
+    data.read_strings()?;
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::read_csv;
+
+    #[test]
+    fn reads_quoted_cells_and_a_header() {
+        let text = "\"x, in kg\",\"the \"\"y\"\"\",b\r\n1,\"2\",4\r\n\r\n3,1,6\r\n2,\"3/4\",\r\n";
+        let data = read_csv(text, None, true, ',').unwrap();
+        assert_eq!(data.variable_names, ["x, in kg", "the \"y\""]);
+        assert_eq!((data.m, data.n), (2, 2));
+        assert_eq!(data.A_numerators, [[1, 2], [3, 1]]);
+        assert_eq!(data.b_numerators, [4, 6]);
+        assert_eq!((data.c_numerators.clone(), data.c_denominators.clone()), (vec![2, 3], vec![1, 4]));
+    }
+
+    #[test]
+    fn reads_the_objective_from_the_chosen_row() {
+        let data = read_csv("2;3;\n1;1;4\n1;3;6\n", Some(1), false, ';').unwrap();
+        assert_eq!(data.c_numerators, [2, 3]);
+        assert_eq!(data.b_numerators, [4, 6]);
+        let message = read_csv("2,3,\n1,1,4\n", Some(5), false, ',').unwrap_err();
+        assert!(message.contains("Row 5"), "{}", message);
+        let message = read_csv("1,1,4\n2,3,1\n", None, false, ',').unwrap_err();
+        assert!(message.starts_with("Cell C2:"), "{}", message);
+    }
+
+    #[test]
+    fn rejects_malformed_files() {
+        let message = read_csv("x,b\n1,1,4\n2,3,\n", None, true, ',').unwrap_err();
+        assert!(message.contains("Row 1 names 2 columns"), "{}", message);
+        let message = read_csv("\n1,1,4\n2,3,\n", None, true, ',').unwrap_err();
+        assert!(message.contains("Row 1 should name the variables"), "{}", message);
+        let message = read_csv("1,\"1,4\n2,3,\n", None, false, ',').unwrap_err();
+        assert!(message.contains("never closed"), "{}", message);
+        let message = read_csv("1,1,4\n2,x,\n", None, false, ',').unwrap_err();
+        assert!(message.starts_with("Cell B2:"), "{}", message);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::m::M;
+
+use num::rational::Ratio;
+use serde::Serialize;
+
+#[derive(Debug, Clone)]
+pub struct Vertex {
+    pub basis_indecies: Vec<usize>,
+    pub solution: Vec<Ratio<i64>>,
+    pub objective: M,
+}
+
+// Every vertex of the feasible region, along with a direction the objective grows along forever if there is one.
+// Vertex objectives are in the problem's own sense, so for a minimization the best vertex is the one with the lowest.
+#[derive(Debug, Clone)]
+pub struct Enumeration {
+    pub vertices: Vec<Vertex>,
+    pub unbounded_direction: Option<Vec<Ratio<i64>>>,
+    pub minimize: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VertexData {
+    pub basis_indecies: Vec<usize>,
+    pub solution: Vec<String>,
+    pub objective: String,
+    pub optimal: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EnumerationData {
+    pub vertices: Vec<VertexData>,
+    pub optimal_objective: Option<String>,
+    pub optimal_vertex_count: usize,
+    pub alternative_optima: bool,
+    pub unbounded_direction: Option<Vec<String>>,
+    pub error: bool,
+    pub error_message: String,
+}
+
+impl EnumerationData {
+    pub fn new(enumeration: Enumeration) -> EnumerationData {
+        let Enumeration { vertices, unbounded_direction, minimize } = enumeration;
+        let objectives = vertices.iter().map(|vertex| &vertex.objective);
+        // No vertex is optimal when the objective grows without bound, however good the best one looks.
+        let best = match (&unbounded_direction, minimize) {
+            (Some(_), _) => None,
+            (None, true) => objectives.min().cloned(),
+            (None, false) => objectives.max().cloned(),
+        };
+        let vertices: Vec<VertexData> = vertices.into_iter()
+                                                .map(|vertex| VertexData {
+                                                    optimal: Some(&vertex.objective) == best.as_ref(),
+                                                    basis_indecies: vertex.basis_indecies,
+                                                    solution: vertex.solution.iter().map(|el| el.to_string()).collect(),
+                                                    objective: vertex.objective.to_string(),
+                                                })
+                                                .collect();
+        let optimal_vertex_count = vertices.iter().filter(|vertex| vertex.optimal).count();
+        let error_message = match &unbounded_direction {
+            Some(direction) => format!("The objective grows without bound along the direction ({}) from a vertex, so the problem is unbounded.",
+                                       direction.iter().map(|el| el.to_string()).collect::<Vec<String>>().join(", ")),
+            None if best.is_none() => String::from("No basic feasible solutions exist, so the problem is infeasible."),
+            None => String::from(""),
+        };
+        EnumerationData {
+            error: best.is_none(),
+            error_message,
+            unbounded_direction: unbounded_direction.map(|direction| direction.iter().map(|el| el.to_string()).collect()),
+            optimal_objective: best.map(|objective| objective.to_string()),
+            optimal_vertex_count,
+            alternative_optima: optimal_vertex_count > 1,
+            vertices,
+        }
+    }
+
+    pub fn error(error_message: String) -> EnumerationData {
+        EnumerationData {
+            vertices: Vec::new(),
+            optimal_objective: None,
+            optimal_vertex_count: 0,
+            alternative_optima: false,
+            unbounded_direction: None,
+            error: true,
+            error_message,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,230 @@
+use crate::report::{column_name, row_name};
+use crate::tableau::Tableau;
+
+use num::rational::Ratio;
+use num::Zero;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Relation {
+    LessEqual,
+    GreaterEqual,
+    Equal,
+}
+
+#[derive(Debug, Clone)]
+pub struct Constraint {
+    pub coefficients: Vec<Ratio<i64>>,
+    pub relation: Relation,
+    pub rhs: Ratio<i64>,
+    pub label: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Region {
+    pub variables: Vec<usize>,
+    pub constraints: Vec<Constraint>,
+}
+
+impl Constraint {
+    pub fn is_satisfied(&self, point: &[Ratio<i64>]) -> bool {
+        let lhs: Ratio<i64> = self.coefficients.iter()
+                                               .zip(point.iter())
+                                               .map(|(a, x)| a * x)
+                                               .sum();
+        match self.relation {
+            Relation::LessEqual => lhs <= self.rhs,
+            Relation::GreaterEqual => lhs >= self.rhs,
+            Relation::Equal => lhs == self.rhs,
+        }
+    }
+
+    pub fn is_tight(&self, point: &[Ratio<i64>]) -> bool {
+        let lhs: Ratio<i64> = self.coefficients.iter()
+                                               .zip(point.iter())
+                                               .map(|(a, x)| a * x)
+                                               .sum();
+        lhs == self.rhs
+    }
+}
+
+impl Region {
+    pub fn dimension(&self) -> usize {
+        self.variables.len()
+    }
+
+    pub fn is_feasible(&self, point: &[Ratio<i64>]) -> bool {
+        self.constraints.iter().all(|constraint| constraint.is_satisfied(point))
+    }
+
+    // Adds x_i <= bound for every variable so that an unbounded region can still be drawn.
+    pub fn bounded(&self, bound: Ratio<i64>) -> Region {
+        let mut region = self.clone();
+        for i in 0..self.dimension() {
+            let mut coefficients = vec![Ratio::zero(); self.dimension()];
+            coefficients[i] = Ratio::from_integer(1);
+            region.constraints.push(Constraint { coefficients, relation: Relation::LessEqual, rhs: bound, label: String::from("bound") });
+        }
+        region
+    }
+
+    pub fn vertices(&self) -> Vec<Vec<Ratio<i64>>> {
+        let dim = self.dimension();
+        let mut vertices: Vec<Vec<Ratio<i64>>> = Vec::new();
+        for subset in combinations(self.constraints.len(), dim) {
+            let rows: Vec<Vec<Ratio<i64>>> = subset.iter().map(|&i| self.constraints[i].coefficients.clone()).collect();
+            let rhs: Vec<Ratio<i64>> = subset.iter().map(|&i| self.constraints[i].rhs).collect();
+            if let Some(point) = solve_square(rows, rhs) {
+                if self.is_feasible(&point) && !vertices.contains(&point) {
+                    vertices.push(point);
+                }
+            }
+        }
+        vertices
+    }
+}
+
+// Splits the columns of the original tableau into decision variables and the slack or surplus columns the user
+// added, so each row can be read back as an inequality over the decision variables alone. When the problem gives
+// decision_variables, every column after them is a slack. Otherwise the slacks are taken to be the trailing columns
+// that have no cost and a single +1 or -1 entry in a row that does not already have one, stopping at the first
+// column that is not, so a decision variable with no cost among the others is not mistaken for a slack.
+pub fn region(t: &Tableau) -> Region {
+    let m = t.original_A.len();
+    let n = t.original_c.len();
+    let mut relations = vec![Relation::Equal; m];
+    let mut slack_cols: Vec<usize> = Vec::new();
+    let known = t.decision_variables.map(|count| count.min(n));
+    for col in (known.unwrap_or(0)..n).rev() {
+        let no_cost = t.original_c[col].m().is_zero() && t.original_c[col].constant().is_zero();
+        let nonzero: Vec<usize> = (0..m).filter(|&row| !t.original_A[row][col].is_zero()).collect();
+        let slack_row = match nonzero.as_slice() {
+            [row] if relations[*row] == Relation::Equal => Some(*row),
+            _ => None,
+        };
+        match slack_row {
+            Some(row) if known.is_some() => {
+                relations[row] = if t.original_A[row][col] > Ratio::zero() { Relation::LessEqual } else { Relation::GreaterEqual };
+                slack_cols.push(col);
+            },
+            Some(row) if no_cost && (t.original_A[row][col] == Ratio::from_integer(1) || t.original_A[row][col] == Ratio::from_integer(-1)) => {
+                relations[row] = if t.original_A[row][col] > Ratio::zero() { Relation::LessEqual } else { Relation::GreaterEqual };
+                slack_cols.push(col);
+            },
+            _ if known.is_some() => {},
+            _ => break,
+        }
+    }
+    let variables: Vec<usize> = (0..n).filter(|col| !slack_cols.contains(col)).collect();
+    let mut constraints: Vec<Constraint> = (0..m).map(|row| Constraint {
+                                                    coefficients: variables.iter().map(|&col| t.original_A[row][col]).collect(),
+                                                    relation: relations[row],
+                                                    rhs: t.original_b[row].constant(),
+                                                    label: row_name(t, row),
+                                                })
+                                                .collect();
+    for i in 0..variables.len() {
+        let mut coefficients = vec![Ratio::zero(); variables.len()];
+        coefficients[i] = Ratio::from_integer(1);
+        constraints.push(Constraint { coefficients, relation: Relation::GreaterEqual, rhs: Ratio::zero(), label: format!("{} ≥ 0", column_name(t, variables[i])) });
+    }
+    Region { variables, constraints }
+}
+
+// The values of the given variables at the basic solution of every recorded step, skipping repeated points.
+pub fn path(t: &Tableau, variables: &[usize]) -> Vec<Vec<Ratio<i64>>> {
+    let mut points: Vec<Vec<Ratio<i64>>> = Vec::new();
+    for step in &t.steps {
+        let point: Vec<Ratio<i64>> = variables.iter()
+                                              .map(|&col| match step.basis_indecies.iter().position(|&index| index == col) {
+                                                  Some(row) => step.b[row].constant(),
+                                                  None => Ratio::zero(),
+                                              })
+                                              .collect();
+        if points.last() != Some(&point) {
+            points.push(point);
+        }
+    }
+    points
+}
+
+pub fn combinations(n: usize, k: usize) -> Vec<Vec<usize>> {
+    let mut result = Vec::new();
+    let mut current = Vec::with_capacity(k);
+    fn extend(start: usize, n: usize, k: usize, current: &mut Vec<usize>, result: &mut Vec<Vec<usize>>) {
+        if current.len() == k {
+            result.push(current.clone());
+            return;
+        }
+        for i in start..n {
+            current.push(i);
+            extend(i + 1, n, k, current, result);
+            current.pop();
+        }
+    }
+    extend(0, n, k, &mut current, &mut result);
+    result
+}
+
+// Solves a square system exactly with Gauss-Jordan elimination, returning None if it is singular.
+pub fn solve_square(mut rows: Vec<Vec<Ratio<i64>>>, mut rhs: Vec<Ratio<i64>>) -> Option<Vec<Ratio<i64>>> {
+    let size = rhs.len();
+    for col in 0..size {
+        let pivot_row = (col..size).find(|&row| !rows[row][col].is_zero())?;
+        rows.swap(col, pivot_row);
+        rhs.swap(col, pivot_row);
+        let pivot = rows[col][col];
+        for el in rows[col].iter_mut() {
+            *el /= pivot;
+        }
+        rhs[col] /= pivot;
+        for row in 0..size {
+            if row != col && !rows[row][col].is_zero() {
+                let factor = rows[row][col];
+                let pivot_row = rows[col].clone();
+                for (el, pivot_el) in rows[row].iter_mut().zip(pivot_row.iter()) {
+                    *el -= factor * pivot_el;
+                }
+                let delta = factor * rhs[col];
+                rhs[row] -= delta;
+            }
+        }
+    }
+    Some(rhs)
+}
+
+// Picks a largest set of linearly independent rows of a system, keeping the earliest row of any that depend on each
+// other. Returns None if the dependent rows contradict the others, in which case the system has no solution.
+pub fn independent_rows(rows: &[Vec<Ratio<i64>>], rhs: &[Ratio<i64>]) -> Option<Vec<usize>> {
+    let mut reduced: Vec<(usize, Vec<Ratio<i64>>, Ratio<i64>)> = Vec::new();
+    let mut kept = Vec::new();
+    for (index, (row, &value)) in rows.iter().zip(rhs.iter()).enumerate() {
+        let mut row = row.clone();
+        let mut value = value;
+        for (pivot_col, pivot_row, pivot_value) in &reduced {
+            let factor = row[*pivot_col];
+            if !factor.is_zero() {
+                for (el, pivot_el) in row.iter_mut().zip(pivot_row.iter()) {
+                    *el -= factor * pivot_el;
+                }
+                value -= factor * pivot_value;
+            }
+        }
+        match row.iter().position(|el| !el.is_zero()) {
+            Some(pivot_col) => {
+                let pivot = row[pivot_col];
+                for el in row.iter_mut() {
+                    *el /= pivot;
+                }
+                reduced.push((pivot_col, row, value / pivot));
+                kept.push(index);
+            },
+            None if !value.is_zero() => return None,
+            None => {},
+        }
+    }
+    Some(kept)
+}
+
+pub fn to_f64(r: &Ratio<i64>) -> f64 {
+    *r.numer() as f64 / *r.denom() as f64
+}
\ No newline at end of file
@@ -0,0 +1,146 @@
+use crate::m::M;
+use crate::scalar::Float;
+use crate::tableau::{Tableau, Step};
+
+use num::rational::Ratio;
+use num::Signed;
+
+pub fn ratio_to_latex(r: &Ratio<i64>) -> String {
+    let sign = if r.is_negative() { "-" } else { "" };
+    let r = r.abs();
+    if r.is_integer() {
+        format!("{}{}", sign, r.numer())
+    } else {
+        format!("{}\\frac{{{}}}{{{}}}", sign, r.numer(), r.denom())
+    }
+}
+
+// A float solve is shown with decimals, since its fractions only stand in for the floats it was computed with.
+fn number_to_latex(r: &Ratio<i64>, decimal: bool) -> String {
+    if decimal {
+        format!("{}{}", if r.is_negative() { "-" } else { "" }, Float(r.numer().abs() as f64 / *r.denom() as f64))
+    } else {
+        ratio_to_latex(r)
+    }
+}
+
+pub fn m_to_latex(m: &M) -> String {
+    value_to_latex(m, false)
+}
+
+fn value_to_latex(m: &M, decimal: bool) -> String {
+    if m.is_zero() {
+        return String::from("0");
+    }
+    let numbered = m.levels() > 1;
+    let mut latex = String::new();
+    for (powers, coefficient) in m.monomials().rev() {
+        let sign = if coefficient.is_negative() { "-" } else { "+" };
+        if latex.is_empty() {
+            if coefficient.is_negative() {
+                latex.push('-');
+            }
+        } else {
+            latex.push_str(&format!(" {} ", sign));
+        }
+        let size = coefficient.abs();
+        let symbol: String = powers.iter()
+                                   .enumerate()
+                                   .filter(|(_, &power)| power > 0)
+                                   .map(|(level, &power)| {
+                                       let name = if numbered { format!("M_{{{}}}", level + 1) } else { String::from("M") };
+                                       if power == 1 { name } else { format!("{}^{{{}}}", name, power) }
+                                   })
+                                   .collect();
+        if powers.is_empty() || size != Ratio::from_integer(1i64) {
+            latex.push_str(&number_to_latex(&size, decimal));
+        }
+        latex.push_str(&symbol);
+    }
+    latex
+}
+
+pub fn escape_latex(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(ch);
+            },
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+// A column named by the problem is set upright as text, and an unnamed one is written x_{1}, x_{2}, ...
+fn column_to_latex(t: &Tableau, col: usize) -> String {
+    match t.variable_names.get(col) {
+        Some(name) => format!("\\text{{{}}}", escape_latex(name)),
+        None => format!("x_{{{}}}", col + 1),
+    }
+}
+
+pub fn step_to_latex(t: &Tableau, step: &Step) -> String {
+    let decimal = t.arithmetic.as_str() == "float";
+    let n = step.reduced_cost.len();
+    let mut latex = format!("\\[\n\\begin{{array}}{{c|{}|c}}\n", "c".repeat(n));
+    let header: Vec<String> = (0..n).map(|col| column_to_latex(t, col)).collect();
+    latex.push_str(&format!(" & {} & b \\\\\n\\hline\n", header.join(" & ")));
+    for (row_index, row) in step.A.iter().enumerate() {
+        let cells: Vec<String> = row.iter()
+                                    .enumerate()
+                                    .map(|(col_index, el)| if step.leaving_var_index == Some(row_index) && step.entering_var_index == Some(col_index) {
+                                            format!("\\boxed{{{}}}", number_to_latex(el, decimal))
+                                        } else {
+                                            number_to_latex(el, decimal)
+                                        })
+                                    .collect();
+        latex.push_str(&format!("{} & {} & {} \\\\\n", column_to_latex(t, step.basis_indecies[row_index]), cells.join(" & "), value_to_latex(&step.b[row_index], decimal)));
+    }
+    let reduced_cost: Vec<String> = step.reduced_cost.iter().map(|el| value_to_latex(el, decimal)).collect();
+    latex.push_str(&format!("\\hline\n & {} & {} \\\\\n", reduced_cost.join(" & "), value_to_latex(&step.obj, decimal)));
+    latex.push_str("\\end{array}\n\\]\n");
+    latex
+}
+
+// Renders every recorded tableau in order, boxing the pivot element of each step. \boxed needs amsmath.
+pub fn tableau_to_latex(t: &Tableau) -> String {
+    let mut latex = String::from("% Requires \\usepackage{amsmath}\n");
+    for step in &t.steps {
+        latex.push('\n');
+        latex.push_str(&step_to_latex(t, step));
+    }
+    if t.error {
+        latex.push_str(&format!("\n\\noindent {}\n", escape_latex(&t.error_message)));
+    }
+    latex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::tableau_to_latex;
+    use crate::tableau::Tableau;
+    use crate::tableau_data::TableauData;
+
+    use num::rational::Ratio;
+
+    #[test]
+    fn boxes_the_pivot_element_of_each_step() {
+        // Maximizes 3x1 + 2x2 subject to 2x1 + x2 <= 4 and x1 + 3x2 <= 6, so the first pivot is the 2 under x1.
+        let r = Ratio::from_integer;
+        let a = vec![vec![r(2), r(1), r(1), r(0)], vec![r(1), r(3), r(0), r(1)]];
+        let mut t = Tableau::new(TableauData::from_standard_form(a, vec![r(4), r(6)], vec![r(3), r(2), r(0), r(0)]));
+        t.setup();
+        t.solve();
+        let latex = tableau_to_latex(&t);
+        assert!(latex.starts_with("% Requires \\usepackage{amsmath}"), "{}", latex);
+        assert_eq!(latex.matches("\\begin{array}").count(), t.steps.len());
+        assert_eq!(latex.matches("\\boxed").count(), t.steps.len() - 1, "{}", latex);
+        assert!(latex.contains("\\boxed{2}"), "{}", latex);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,23 @@
+pub mod certificate;
+pub mod compact;
+pub mod csv;
+pub mod enumeration;
+pub mod geometry;
+pub mod latex;
+pub mod lp;
+pub mod m;
+pub mod mps;
+pub mod parse;
+pub mod pivot_feedback;
+pub mod plot;
+pub mod polytope;
+pub mod problem;
+pub mod report;
+pub mod scalar;
+pub mod session;
+pub mod solver;
+pub mod tableau_data;
+pub mod tableau;
+pub mod verification;
+
+pub use tableau::Tableau;
@@ -0,0 +1,537 @@
+use crate::geometry::Relation;
+use crate::parse::parse_ratio;
+use crate::problem::{Problem, format_decimal};
+
+use num::rational::Ratio;
+use num::{Signed, Zero};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(String),
+    Name(String),
+    Sign(bool),
+    Colon,
+    Relation(Relation),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    Objective,
+    Constraints,
+    Bounds,
+    General,
+    Binary,
+}
+
+const KEYWORDS: [(&str, Section); 18] = [
+    ("maximize", Section::Objective), ("maximum", Section::Objective), ("max", Section::Objective),
+    ("minimize", Section::Objective), ("minimum", Section::Objective), ("min", Section::Objective),
+    ("subject to", Section::Constraints), ("such that", Section::Constraints),
+    ("s.t.", Section::Constraints), ("st", Section::Constraints),
+    ("bounds", Section::Bounds), ("bound", Section::Bounds),
+    ("generals", Section::General), ("general", Section::General), ("gen", Section::General),
+    ("binaries", Section::Binary), ("binary", Section::Binary), ("bin", Section::Binary),
+];
+
+// Names may use most printable characters, but not the ones that separate terms.
+fn is_name_char(ch: char) -> bool {
+    !ch.is_whitespace() && !"+-:<>=".contains(ch)
+}
+
+fn tokenize(text: &str, line: usize) -> Result<Vec<(Token, usize)>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+    while index < chars.len() {
+        let ch = chars[index];
+        let start = index;
+        index += 1;
+        let token = match ch {
+            _ if ch.is_whitespace() => continue,
+            '+' | '-' => Token::Sign(ch == '+'),
+            ':' => Token::Colon,
+            '<' | '>' | '=' => {
+                if matches!(chars.get(index), Some('<') | Some('>') | Some('=')) {
+                    index += 1;
+                }
+                let relation: String = chars[start..index].iter().collect();
+                match relation.as_str() {
+                    "<" | "<=" | "=<" => Token::Relation(Relation::LessEqual),
+                    ">" | ">=" | "=>" => Token::Relation(Relation::GreaterEqual),
+                    "=" => Token::Relation(Relation::Equal),
+                    _ => return Err(format!("Line {}: '{}' is not a comparison.", line, relation)),
+                }
+            },
+            _ if ch.is_ascii_digit() || ch == '.' => {
+                while index < chars.len() && (chars[index].is_ascii_digit() || chars[index] == '.') {
+                    index += 1;
+                }
+                // An e after a number only starts an exponent when digits follow, so "2e1" is 20 while "2 e" is 2e.
+                if matches!(chars.get(index), Some('e') | Some('E')) {
+                    let digits = if matches!(chars.get(index + 1), Some('+') | Some('-')) { index + 2 } else { index + 1 };
+                    if chars.get(digits).is_some_and(|ch| ch.is_ascii_digit()) {
+                        index = digits;
+                        while index < chars.len() && chars[index].is_ascii_digit() {
+                            index += 1;
+                        }
+                    }
+                }
+                Token::Number(chars[start..index].iter().collect())
+            },
+            _ => {
+                while index < chars.len() && is_name_char(chars[index]) {
+                    index += 1;
+                }
+                Token::Name(chars[start..index].iter().collect())
+            },
+        };
+        tokens.push((token, line));
+    }
+    Ok(tokens)
+}
+
+fn is_infinity(name: &str) -> bool {
+    name.eq_ignore_ascii_case("inf") || name.eq_ignore_ascii_case("infinity")
+}
+
+struct Parser<'a> {
+    tokens: &'a [(Token, usize)],
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn done(&self) -> bool {
+        self.position >= self.tokens.len()
+    }
+
+    fn error(&self, message: &str) -> String {
+        match self.tokens.get(self.position).or_else(|| self.tokens.last()) {
+            Some((_, line)) => format!("Line {}: {}", line, message),
+            None => String::from(message),
+        }
+    }
+
+    // Reads "name:" when the next two tokens are a name and a colon.
+    fn label(&mut self) -> Option<String> {
+        match (self.tokens.get(self.position), self.tokens.get(self.position + 1)) {
+            (Some((Token::Name(name), _)), Some((Token::Colon, _))) => {
+                self.position += 2;
+                Some(name.clone())
+            },
+            _ => None,
+        }
+    }
+
+    fn relation(&mut self, message: &str) -> Result<Relation, String> {
+        match self.peek() {
+            Some(&Token::Relation(relation)) => {
+                self.position += 1;
+                Ok(relation)
+            },
+            _ => Err(self.error(message)),
+        }
+    }
+
+    fn variable(&mut self, problem: &mut Problem) -> Result<usize, String> {
+        match self.peek() {
+            Some(Token::Name(name)) if !is_infinity(name) => {
+                let col = problem.variable_index(name);
+                self.position += 1;
+                Ok(col)
+            },
+            _ => Err(self.error("Expected a variable.")),
+        }
+    }
+
+    fn signs(&mut self) -> bool {
+        let mut positive = true;
+        while let Some(Token::Sign(sign)) = self.peek() {
+            positive = positive == *sign;
+            self.position += 1;
+        }
+        positive
+    }
+
+    // Reads a signed number. Infinity is returned as None, which is the missing bound on either side.
+    fn value(&mut self) -> Result<Option<Ratio<i64>>, String> {
+        let positive = self.signs();
+        match self.peek() {
+            Some(Token::Number(text)) => {
+                let value = parse_ratio(text).map_err(|message| self.error(&message))?;
+                self.position += 1;
+                Ok(Some(if positive { value } else { -value }))
+            },
+            Some(Token::Name(name)) if is_infinity(name) => {
+                self.position += 1;
+                Ok(None)
+            },
+            _ => Err(self.error("Expected a number.")),
+        }
+    }
+
+    // Reads terms such as "3 x - y + 2.5 z". It stops before the first term without a variable and before the label
+    // of the next row, leaving the caller to decide whether what follows is allowed.
+    fn expression(&mut self, problem: &mut Problem) -> Result<Vec<(usize, Ratio<i64>)>, String> {
+        let mut terms = Vec::new();
+        loop {
+            let start = self.position;
+            if self.label().is_some() {
+                self.position = start;
+                break;
+            }
+            let positive = self.signs();
+            let coefficient = match self.peek() {
+                Some(Token::Number(text)) => {
+                    let value = parse_ratio(text).map_err(|message| self.error(&message))?;
+                    self.position += 1;
+                    value
+                },
+                _ => Ratio::from_integer(1),
+            };
+            match self.peek() {
+                Some(Token::Name(name)) if !is_infinity(name) => {
+                    let col = problem.variable_index(name);
+                    self.position += 1;
+                    terms.push((col, if positive { coefficient } else { -coefficient }));
+                },
+                _ => {
+                    self.position = start;
+                    break;
+                },
+            }
+        }
+        Ok(terms)
+    }
+}
+
+fn read_objective(tokens: &[(Token, usize)], problem: &mut Problem) -> Result<(), String> {
+    let mut parser = Parser { tokens, position: 0 };
+    if let Some(name) = parser.label() {
+        problem.objective_name = name;
+    }
+    for (col, coefficient) in parser.expression(problem)? {
+        problem.objective[col] += coefficient;
+    }
+    if !parser.done() {
+        return Err(parser.error("A constant in the objective is not supported."));
+    }
+    Ok(())
+}
+
+fn read_constraints(tokens: &[(Token, usize)], problem: &mut Problem) -> Result<(), String> {
+    let mut parser = Parser { tokens, position: 0 };
+    while !parser.done() {
+        let name = parser.label().unwrap_or_else(|| format!("R{}", problem.constraints.len() + 1));
+        if problem.find_constraint(&name).is_some() {
+            return Err(parser.error(&format!("Constraint '{}' is declared twice.", name)));
+        }
+        let terms = parser.expression(problem)?;
+        let relation = parser.relation("Expected <=, >= or = after the terms of a constraint. Constants belong on the right hand side.")?;
+        let rhs = parser.value()?.ok_or_else(|| parser.error("The right hand side of a constraint has to be finite."))?;
+        let row = problem.add_constraint(&name, relation, rhs);
+        for (col, coefficient) in terms {
+            problem.constraints[row].coefficients[col] += coefficient;
+        }
+    }
+    Ok(())
+}
+
+// A value on the left of the variable, as in "3 <= x", bounds it the same way as "x >= 3".
+fn set_bound(problem: &mut Problem, col: usize, relation: Relation, value: Option<Ratio<i64>>, value_on_left: bool) {
+    let variable = &mut problem.variables[col];
+    match (relation, value_on_left) {
+        (Relation::LessEqual, false) | (Relation::GreaterEqual, true) => variable.upper = value,
+        (Relation::GreaterEqual, false) | (Relation::LessEqual, true) => variable.lower = value,
+        (Relation::Equal, _) => {
+            variable.lower = value;
+            variable.upper = value;
+        },
+    }
+}
+
+// Reads bounds written as "x <= 4", "-1 <= x <= 4", "x = 2", "x >= -inf" or "x free".
+fn read_bounds(tokens: &[(Token, usize)], problem: &mut Problem) -> Result<(), String> {
+    let mut parser = Parser { tokens, position: 0 };
+    while !parser.done() {
+        if let Ok(col) = parser.variable(problem) {
+            match parser.peek() {
+                Some(Token::Name(word)) if word.eq_ignore_ascii_case("free") => {
+                    parser.position += 1;
+                    problem.variables[col].lower = None;
+                    problem.variables[col].upper = None;
+                },
+                _ => {
+                    let relation = parser.relation("Expected a comparison or free after the variable of a bound.")?;
+                    let value = parser.value()?;
+                    set_bound(problem, col, relation, value, false);
+                },
+            }
+        } else {
+            let value = parser.value()?;
+            let relation = parser.relation("Expected a comparison in a bound.")?;
+            let col = parser.variable(problem)?;
+            set_bound(problem, col, relation, value, true);
+            if let Some(Token::Relation(_)) = parser.peek() {
+                let relation = parser.relation("Expected a comparison in a bound.")?;
+                let value = parser.value()?;
+                set_bound(problem, col, relation, value, false);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn read_integers(tokens: &[(Token, usize)], problem: &mut Problem, binary: bool) -> Result<(), String> {
+    let mut parser = Parser { tokens, position: 0 };
+    while !parser.done() {
+        let col = parser.variable(problem)?;
+        let variable = &mut problem.variables[col];
+        variable.integer = true;
+        if binary {
+            variable.lower = Some(Ratio::zero());
+            variable.upper = Some(Ratio::from_integer(1));
+        }
+    }
+    Ok(())
+}
+
+// Reads a problem in CPLEX LP format. Comments start with a backslash and any section may span several lines.
+// Variables listed under General or Binary are marked as integer, but only the LP relaxation is solved.
+pub fn read_lp(text: &str) -> Result<Problem, String> {
+    let mut problem = Problem::new("");
+    let mut sense: Option<bool> = None;
+    let mut sections: Vec<(Section, Vec<(Token, usize)>)> = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        // CPLEX writes the name of the problem as a comment, which is the only place the format has for it.
+        if let Some(name) = line.trim().strip_prefix('\\').and_then(|comment| comment.trim().strip_prefix("Problem name:")) {
+            problem.name = name.trim().to_string();
+        }
+        let line = line.split('\\').next().unwrap_or("").trim();
+        let lower = line.to_lowercase();
+        if lower == "end" {
+            break;
+        }
+        // A keyword only starts a section when it is a word of its own that is not followed by a sign, a comparison, a
+        // colon or free, so st, bin or min can still begin a line as a name, as in "st: x <= 4", "bin + x <= 1" or
+        // "min free". Nothing can come before the objective, so the first Maximize or Minimize is always a keyword.
+        let keyword = KEYWORDS.iter().find(|&&(keyword, section)| {
+            let after = match lower.strip_prefix(keyword) {
+                Some(after) => after,
+                None => return false,
+            };
+            let next_word = after.split_whitespace().next().unwrap_or("");
+            let continues_line = next_word.starts_with(['+', '-', '<', '>', '=', ':']) || next_word == "free";
+            (after.is_empty() || after.starts_with(char::is_whitespace)) && (!continues_line || sense.is_none() && section == Section::Objective)
+        });
+        let mut rest = line;
+        if let Some(&(keyword, section)) = keyword {
+            if section == Section::Objective {
+                if sense.is_some() {
+                    return Err(format!("Line {}: The file has more than one objective.", number + 1));
+                }
+                sense = Some(keyword.starts_with("max"));
+            }
+            sections.push((section, Vec::new()));
+            rest = &line[keyword.len()..];
+        }
+        let tokens = tokenize(rest, number + 1)?;
+        match sections.last_mut() {
+            Some((_, section_tokens)) => section_tokens.extend(tokens),
+            None if tokens.is_empty() => {},
+            None => return Err(format!("Line {}: Expected Maximize or Minimize before the objective.", number + 1)),
+        }
+    }
+    problem.maximize = sense.ok_or_else(|| String::from("The file needs a Maximize or Minimize section."))?;
+    // Sections are read in this order so that variables are numbered as they first appear in the objective and rows.
+    for kind in [Section::Objective, Section::Constraints, Section::Bounds, Section::General, Section::Binary] {
+        for (_, tokens) in sections.iter().filter(|(section, _)| *section == kind) {
+            match kind {
+                Section::Objective => read_objective(tokens, &mut problem)?,
+                Section::Constraints => read_constraints(tokens, &mut problem)?,
+                Section::Bounds => read_bounds(tokens, &mut problem)?,
+                Section::General => read_integers(tokens, &mut problem, false)?,
+                Section::Binary => read_integers(tokens, &mut problem, true)?,
+            }
+        }
+    }
+    Ok(problem)
+}
+
+fn check_name(name: &str) -> Result<(), String> {
+    let starts_badly = name.starts_with(|ch: char| ch.is_ascii_digit() || ch == '.');
+    if name.is_empty() || starts_badly || !name.chars().all(is_name_char) || is_infinity(name) {
+        return Err(format!("'{}' cannot be written as an LP name. Names cannot start with a digit or a period, or contain spaces or any of + - : < > =.", name));
+    }
+    Ok(())
+}
+
+// Writes "3 x - y + 0.5 z", wrapping long expressions since LP readers limit the length of a line.
+fn expression(problem: &Problem, coefficients: &[Ratio<i64>]) -> String {
+    let mut terms: Vec<String> = Vec::new();
+    for (variable, coefficient) in problem.variables.iter().zip(coefficients.iter()).filter(|(_, coefficient)| !coefficient.is_zero()) {
+        let sign = if coefficient.is_negative() { "- " } else if terms.is_empty() { "" } else { "+ " };
+        let magnitude = coefficient.abs();
+        if magnitude == Ratio::from_integer(1) {
+            terms.push(format!("{}{}", sign, variable.name));
+        } else {
+            terms.push(format!("{}{} {}", sign, format_decimal(&magnitude), variable.name));
+        }
+    }
+    if terms.is_empty() {
+        return problem.variables.first().map(|variable| format!("0 {}", variable.name)).unwrap_or_default();
+    }
+    let mut text = String::new();
+    let mut line_len = 0;
+    for term in terms {
+        if line_len > 0 && line_len + term.len() > 72 {
+            text.push_str("\n   ");
+            line_len = 0;
+        } else if line_len > 0 {
+            text.push(' ');
+        }
+        line_len += term.len() + 1;
+        text.push_str(&term);
+    }
+    text
+}
+
+pub fn write_lp(problem: &Problem) -> Result<String, String> {
+    for name in problem.variables.iter().map(|variable| &variable.name)
+                                 .chain(problem.constraints.iter().map(|constraint| &constraint.label))
+                                 .chain(std::iter::once(&problem.objective_name)) {
+        check_name(name)?;
+    }
+    let mut lines: Vec<String> = Vec::new();
+    if !problem.name.is_empty() {
+        lines.push(format!("\\ Problem name: {}", problem.name));
+    }
+    lines.push(String::from(if problem.maximize { "Maximize" } else { "Minimize" }));
+    lines.push(format!(" {}: {}", problem.objective_name, expression(problem, &problem.objective)));
+    lines.push(String::from("Subject To"));
+    for constraint in &problem.constraints {
+        let relation = match constraint.relation {
+            Relation::LessEqual => "<=",
+            Relation::GreaterEqual => ">=",
+            Relation::Equal => "=",
+        };
+        lines.push(format!(" {}: {} {} {}", constraint.label, expression(problem, &constraint.coefficients), relation, format_decimal(&constraint.rhs)));
+    }
+    let mut bounds: Vec<String> = Vec::new();
+    for variable in &problem.variables {
+        match (variable.lower, variable.upper) {
+            (Some(lower), Some(upper)) if lower == upper => bounds.push(format!(" {} = {}", variable.name, format_decimal(&lower))),
+            (None, None) => bounds.push(format!(" {} free", variable.name)),
+            (Some(lower), None) if lower.is_zero() => {},
+            (Some(lower), None) => bounds.push(format!(" {} >= {}", variable.name, format_decimal(&lower))),
+            (lower, Some(upper)) => {
+                let lower = lower.map(|lower| format_decimal(&lower)).unwrap_or_else(|| String::from("-inf"));
+                bounds.push(format!(" {} <= {} <= {}", lower, variable.name, format_decimal(&upper)));
+            },
+        }
+    }
+    if !bounds.is_empty() {
+        lines.push(String::from("Bounds"));
+        lines.extend(bounds);
+    }
+    let integers: Vec<&str> = problem.variables.iter().filter(|variable| variable.integer).map(|variable| variable.name.as_str()).collect();
+    if !integers.is_empty() {
+        lines.push(String::from("General"));
+        lines.push(format!(" {}", integers.join(" ")));
+    }
+    lines.push(String::from("End"));
+    Ok(lines.join("\n") + "\n")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{read_lp, write_lp};
+    use crate::geometry::Relation;
+    use crate::problem::{Problem, Variable};
+
+    use num::rational::Ratio;
+
+    fn r(value: i64) -> Ratio<i64> {
+        Ratio::from_integer(value)
+    }
+
+    fn bounds(problem: &Problem, name: &str) -> (Option<Ratio<i64>>, Option<Ratio<i64>>, bool) {
+        let variable = &problem.variables[problem.find_variable(name).unwrap()];
+        (variable.lower, variable.upper, variable.integer)
+    }
+
+    #[test]
+    fn reads_keywords_as_names_where_they_cannot_start_a_section() {
+        let text = "Maximize\n obj: 2 min + 3 st - bin\nSubject To\n st: min + st <= 4\n bin + st >= 1\n min: st - bin = 0\n\
+                    Bounds\n st <= 3\n bin free\nEnd\n";
+        let problem = read_lp(text).unwrap();
+        let names: Vec<&str> = problem.variables.iter().map(|variable| variable.name.as_str()).collect();
+        assert_eq!(names, vec!["min", "st", "bin"]);
+        assert_eq!(problem.objective, vec![r(2), r(3), r(-1)]);
+        let labels: Vec<&str> = problem.constraints.iter().map(|constraint| constraint.label.as_str()).collect();
+        assert_eq!(labels, vec!["st", "R2", "min"]);
+        assert_eq!(problem.constraints[1].coefficients, vec![r(0), r(1), r(1)]);
+        assert_eq!(bounds(&problem, "st"), (Some(r(0)), Some(r(3)), false));
+        assert_eq!(bounds(&problem, "bin"), (None, None, false));
+    }
+
+    #[test]
+    fn reads_each_form_of_bound() {
+        let text = "Minimize\n x + y + z + w\nSubject To\n c1: x + y + z + w >= 1\nBounds\n -inf <= x <= 4\n y free\n 3 <= z\n\
+                    w = 2.5\nEnd\n";
+        let problem = read_lp(text).unwrap();
+        assert!(!problem.maximize);
+        assert_eq!(bounds(&problem, "x"), (None, Some(r(4)), false));
+        assert_eq!(bounds(&problem, "y"), (None, None, false));
+        assert_eq!(bounds(&problem, "z"), (Some(r(3)), None, false));
+        assert_eq!(bounds(&problem, "w"), (Some(Ratio::new(5, 2)), Some(Ratio::new(5, 2)), false));
+    }
+
+    #[test]
+    fn marks_general_and_binary_variables() {
+        let text = "Maximize\n x + y + z\nSubject To\n x + y + z <= 4\nBounds\n x <= 10\nGeneral\n x\nBinary\n y\nEnd\n";
+        let problem = read_lp(text).unwrap();
+        assert_eq!(bounds(&problem, "x"), (Some(r(0)), Some(r(10)), true));
+        assert_eq!(bounds(&problem, "y"), (Some(r(0)), Some(r(1)), true));
+        assert_eq!(bounds(&problem, "z"), (Some(r(0)), None, false));
+    }
+
+    #[test]
+    fn reads_back_what_it_writes() {
+        let mut problem = Problem::new("trip");
+        problem.maximize = true;
+        problem.objective_name = String::from("profit");
+        for name in ["x", "y", "z", "w"] {
+            problem.variable_index(name);
+        }
+        problem.objective = vec![r(3), Ratio::new(-1, 4), r(0), r(2)];
+        let index = problem.add_constraint("c1", Relation::LessEqual, r(10));
+        problem.constraints[index].coefficients = vec![r(1), r(2), r(0), r(1)];
+        let index = problem.add_constraint("c2", Relation::GreaterEqual, r(-2));
+        problem.constraints[index].coefficients = vec![r(0), r(1), r(-1), r(0)];
+        let index = problem.add_constraint("c3", Relation::Equal, Ratio::new(1, 2));
+        problem.constraints[index].coefficients = vec![r(1), r(0), r(1), r(0)];
+        problem.variables[1] = Variable { lower: None, upper: None, ..Variable::new("y") };
+        problem.variables[2] = Variable { lower: None, upper: Some(r(5)), integer: true, ..Variable::new("z") };
+        problem.variables[3] = Variable { lower: Some(r(2)), upper: Some(r(2)), ..Variable::new("w") };
+        let read = read_lp(&write_lp(&problem).unwrap()).unwrap();
+        assert_eq!((&read.name, read.maximize, &read.objective_name), (&problem.name, problem.maximize, &problem.objective_name));
+        // Variables are numbered as they first appear, so z, which is not in the objective, comes back last.
+        let order: Vec<usize> = problem.variables.iter().map(|variable| read.find_variable(&variable.name).unwrap()).collect();
+        assert_eq!(order, vec![0, 1, 3, 2]);
+        for (col, variable) in order.iter().zip(problem.variables.iter()) {
+            let read_variable = &read.variables[*col];
+            assert_eq!((read_variable.lower, read_variable.upper, read_variable.integer), (variable.lower, variable.upper, variable.integer));
+        }
+        let reorder = |values: &[Ratio<i64>]| order.iter().map(|&col| values[col]).collect::<Vec<Ratio<i64>>>();
+        assert_eq!(reorder(&read.objective), problem.objective);
+        for (read, constraint) in read.constraints.iter().zip(problem.constraints.iter()) {
+            assert_eq!((&read.label, read.relation, read.rhs), (&constraint.label, constraint.relation, constraint.rhs));
+            assert_eq!(reorder(&read.coefficients), constraint.coefficients);
+        }
+    }
+}
@@ -1,202 +1,570 @@
 #![allow(non_snake_case)]
 
+use crate::scalar::{Scalar, Float};
+
 use num::rational::Ratio;
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryFrom;
 
+// A polynomial in one or more symbolic big-M constants M1 >> M2 >> ... >> 1, where every level is larger than any
+// multiple of the levels below it. Each monomial is keyed by its vector of powers, with trailing zero powers trimmed,
+// so the constant is [], M1 is [1], M1^2 is [2] and M2 is [0, 1]. The ordering of these keys is the ordering of the
+// monomials' sizes, so the largest monomial with a differing coefficient decides a comparison. A problem with a single
+// big-M only ever uses the first level, which is displayed as plain M.
 #[derive(Eq, Debug, Clone)]
-pub struct M {
-    pub M: Ratio<i64>,
-    pub constant: Ratio<i64>,
+pub struct M<T: Scalar = Ratio<i64>> {
+    terms: BTreeMap<Vec<usize>, T>,
 }
 
-impl M {
-    pub fn new(M: Ratio<i64>, constant: Ratio<i64>) -> M {
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MTerm {
+    pub powers: Vec<usize>,
+    pub numerator: i64,
+    pub denominator: i64,
+}
+
+impl<T: Scalar> M<T> {
+    pub fn new(M: T, constant: T) -> M<T> {
+        M::from_levels(constant, vec![M])
+    }
+
+    pub fn zero() -> M<T> {
+        M { terms: BTreeMap::new() }
+    }
+
+    // A value with one coefficient per level, so levels[0] multiplies M1 and levels[1] multiplies M2.
+    pub fn from_levels(constant: T, levels: Vec<T>) -> M<T> {
+        let mut terms = vec![(Vec::new(), constant)];
+        for (level, coefficient) in levels.into_iter().enumerate() {
+            let mut powers = vec![0; level + 1];
+            powers[level] = 1;
+            terms.push((powers, coefficient));
+        }
+        M::from_monomials(terms)
+    }
+
+    // A polynomial in the first level only, where terms[k] is the coefficient of M^k.
+    pub fn from_terms(terms: Vec<T>) -> M<T> {
+        M::from_monomials(terms.into_iter()
+                               .enumerate()
+                               .map(|(power, coefficient)| (if power == 0 { Vec::new() } else { vec![power] }, coefficient))
+                               .collect())
+    }
+
+    pub fn from_monomials(monomials: Vec<(Vec<usize>, T)>) -> M<T> {
+        let mut terms: BTreeMap<Vec<usize>, T> = BTreeMap::new();
+        for (mut powers, coefficient) in monomials {
+            while powers.last() == Some(&0) {
+                powers.pop();
+            }
+            *terms.entry(powers).or_insert_with(T::zero) += coefficient;
+        }
+        terms.retain(|_, coefficient| !coefficient.is_zero());
         M {
-            M,
-            constant,
+            terms,
+        }
+    }
+
+    // The non-zero monomials from smallest to largest.
+    pub fn monomials(&self) -> impl DoubleEndedIterator<Item = (&Vec<usize>, &T)> {
+        self.terms.iter()
+    }
+
+    // The coefficient of M1^power.
+    pub fn term(&self, power: usize) -> T {
+        let powers = if power == 0 { Vec::new() } else { vec![power] };
+        self.terms.get(&powers).cloned().unwrap_or_else(T::zero)
+    }
+
+    // The coefficient of the given level on its own. Levels start at 1 for M1, and level 0 has no M in it, so it is
+    // always 0; use constant() for the part without M.
+    pub fn level(&self, level: usize) -> T {
+        if level == 0 {
+            return T::zero();
         }
+        let mut powers = vec![0; level];
+        powers[level - 1] = 1;
+        self.terms.get(&powers).cloned().unwrap_or_else(T::zero)
+    }
+
+    // The number of levels this value uses.
+    pub fn levels(&self) -> usize {
+        self.terms.keys().map(|powers| powers.len()).max().unwrap_or(0)
+    }
+
+    pub fn constant(&self) -> T {
+        self.term(0)
+    }
+
+    // The coefficient of the first level of M.
+    pub fn m(&self) -> T {
+        self.term(1)
+    }
+
+    pub fn degree(&self) -> usize {
+        self.terms.keys().map(|powers| powers.iter().sum()).max().unwrap_or(0)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    // The number this value takes when every level of M is replaced by the given number.
+    pub fn evaluate(&self, value: &T) -> T {
+        self.terms.iter()
+                  .map(|(powers, coefficient)| (0..powers.iter().sum::<usize>()).fold(*coefficient, |acc, _| acc * *value))
+                  .sum()
+    }
+
+    // Drops every coefficient whose size is at most the tolerance, so that rounding error left over from floating
+    // point pivots is not mistaken for a real value.
+    pub fn snap(&self, tolerance: &T) -> M<T> {
+        M::from_monomials(self.terms.iter()
+                                    .filter(|(_, coefficient)| coefficient.abs() > *tolerance)
+                                    .map(|(powers, coefficient)| (powers.clone(), *coefficient))
+                                    .collect())
+    }
+
+    pub fn map<U: Scalar>(&self, f: impl Fn(&T) -> U) -> M<U> {
+        M::from_monomials(self.terms.iter().map(|(powers, coefficient)| (powers.clone(), f(coefficient))).collect())
+    }
+
+    // Like map, but stops at the first coefficient that cannot be converted.
+    pub fn try_map<U: Scalar, E>(&self, f: impl Fn(&T) -> Result<U, E>) -> Result<M<U>, E> {
+        let monomials = self.terms.iter()
+                                  .map(|(powers, coefficient)| Ok((powers.clone(), f(coefficient)?)))
+                                  .collect::<Result<Vec<(Vec<usize>, U)>, E>>()?;
+        Ok(M::from_monomials(monomials))
+    }
+
+    fn combine(&self, other: &M<T>, negate: bool) -> M<T> {
+        M::from_monomials(self.terms.iter()
+                                    .map(|(powers, coefficient)| (powers.clone(), *coefficient))
+                                    .chain(other.terms.iter().map(|(powers, coefficient)| (powers.clone(), if negate { -*coefficient } else { *coefficient })))
+                                    .collect())
     }
 }
 
-impl std::ops::Mul<&M> for &Ratio<i64> {
-    type Output = M;
+impl<T: Scalar> Default for M<T> {
+    fn default() -> M<T> {
+        M::zero()
+    }
+}
 
-    fn mul(self, rhs: &M) -> M {
-        M {
-            M: rhs.M * self,
-            constant: rhs.constant * self,
+impl M {
+    // The value written with decimals instead of fractions, the way a float result is reported.
+    pub fn to_decimal(&self) -> String {
+        self.map(|el| Float(el.to_f64())).to_string()
+    }
+}
+
+impl TryFrom<Vec<MTerm>> for M {
+    type Error = String;
+
+    fn try_from(terms: Vec<MTerm>) -> Result<M, String> {
+        if terms.iter().any(|term| term.denominator == 0) {
+            return Err(String::from("Denominators cannot be zero."));
         }
+        Ok(M::from_monomials(terms.into_iter().map(|term| (term.powers, Ratio::new(term.numerator, term.denominator))).collect()))
+    }
+}
+
+impl From<M> for Vec<MTerm> {
+    fn from(m: M) -> Vec<MTerm> {
+        m.terms.into_iter()
+               .map(|(powers, coefficient)| MTerm { powers, numerator: *coefficient.numer(), denominator: *coefficient.denom() })
+               .collect()
     }
 }
 
-impl std::ops::Mul<&M> for &M {
-    type Output = M;
+impl Serialize for M {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Vec::<MTerm>::from(self.clone()).serialize(serializer)
+    }
+}
 
-    fn mul(self, rhs: &M) -> M {
-        if self.M == Ratio::new(0i64,1) {
-            M {
-                M: rhs.M * self.constant,
-                constant: rhs.constant * self.constant,
-            }
-        } else if rhs.M == Ratio::new(0i64,1) {
-            M {
-                M: self.M * rhs.constant,
-                constant: self.constant * rhs.constant,
-            }
-        } else {
-            M {
-                M: Ratio::new(0i64,1),
-                constant: Ratio::new(0i64,1),
+impl<'de> Deserialize<'de> for M {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<M, D::Error> {
+        M::try_from(Vec::<MTerm>::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+macro_rules! scalar_times_m {
+    ($scalar:ty) => {
+        impl std::ops::Mul<&M<$scalar>> for &$scalar {
+            type Output = M<$scalar>;
+
+            fn mul(self, rhs: &M<$scalar>) -> M<$scalar> {
+                rhs.map(|el| *el * *self)
             }
         }
+    };
+}
+
+scalar_times_m!(Ratio<i64>);
+scalar_times_m!(Float);
+
+impl<T: Scalar> std::ops::Mul<&T> for &M<T> {
+    type Output = M<T>;
+
+    fn mul(self, rhs: &T) -> M<T> {
+        self.map(|el| *el * *rhs)
     }
 }
 
-impl std::ops::Sub<M> for &M {
-    type Output = M;
+impl<T: Scalar> std::ops::Mul<&M<T>> for &M<T> {
+    type Output = M<T>;
 
-    fn sub(self, rhs: M) -> M {
-        M {
-            M: self.M - rhs.M,
-            constant: self.constant - rhs.constant,
+    fn mul(self, rhs: &M<T>) -> M<T> {
+        let mut monomials = Vec::with_capacity(self.terms.len() * rhs.terms.len());
+        for (a_powers, a) in &self.terms {
+            for (b_powers, b) in &rhs.terms {
+                let powers: Vec<usize> = (0..a_powers.len().max(b_powers.len()))
+                                             .map(|level| a_powers.get(level).unwrap_or(&0) + b_powers.get(level).unwrap_or(&0))
+                                             .collect();
+                monomials.push((powers, *a * *b));
+            }
         }
+        M::from_monomials(monomials)
     }
 }
 
-impl std::ops::Neg for &M {
-    type Output = M;
+impl<T: Scalar> std::ops::Sub<M<T>> for &M<T> {
+    type Output = M<T>;
 
-    fn neg(self) -> M {
-        M {
-            M: -self.M,
-            constant: -self.constant,
-        }
+    fn sub(self, rhs: M<T>) -> M<T> {
+        self.combine(&rhs, true)
+    }
+}
+
+impl<T: Scalar> std::ops::Neg for &M<T> {
+    type Output = M<T>;
+
+    fn neg(self) -> M<T> {
+        self.map(|el| -*el)
     }
 }
 
-impl std::iter::Sum<Self> for M {
-    fn sum<I>(iter: I) -> Self 
+impl<T: Scalar> std::iter::Sum<Self> for M<T> {
+    fn sum<I>(iter: I) -> Self
     where
         I: Iterator<Item = Self>,
         {
-            iter.fold(Self { M: Ratio::from_integer(0i64), constant: Ratio::from_integer(0i64), }, |a ,b| Self { M: a.M + b.M, constant: a.constant + b.constant, })
+            iter.fold(M::zero(), |a, b| a.combine(&b, false))
         }
 }
 
-impl std::ops::Div<&Ratio<i64>> for &M {
-    type Output = M;
+impl<T: Scalar> std::ops::Div<&T> for &M<T> {
+    type Output = M<T>;
 
-    fn div(self, rhs: &Ratio<i64>) -> M {
-        M {
-            M: self.M / rhs,
-            constant: self.constant / rhs,
-        }
+    fn div(self, rhs: &T) -> M<T> {
+        self.map(|el| *el / *rhs)
     }
 }
 
-impl std::ops::Div<&Ratio<i64>> for M {
+impl<T: Scalar> std::ops::Div<&T> for M<T> {
     type Output = Self;
 
-    fn div(self, rhs: &Ratio<i64>) -> Self {
-        M {
-            M: self.M / rhs,
-            constant: self.constant / rhs,
-        }
+    fn div(self, rhs: &T) -> Self {
+        self.map(|el| *el / *rhs)
     }
 }
 
-impl std::ops::AddAssign<&M> for M {
+impl<T: Scalar> std::ops::AddAssign<&M<T>> for M<T> {
     fn add_assign(&mut self, rhs: &Self) {
-        self.M += rhs.M;
-        self.constant += rhs.constant;
+        *self = self.combine(rhs, false);
     }
 }
 
-impl std::ops::SubAssign<&M> for M {
+impl<T: Scalar> std::ops::SubAssign<&M<T>> for M<T> {
     fn sub_assign(&mut self, rhs: &Self) {
-        self.M -= rhs.M;
-        self.constant -= rhs.constant;
+        *self = self.combine(rhs, true);
     }
 }
 
-impl std::ops::MulAssign<i64> for M {
+impl<T: Scalar> std::ops::MulAssign<i64> for M<T> {
     fn mul_assign(&mut self, rhs: i64) {
-        self.M *= rhs;
-        self.constant *= rhs;
+        let rhs = T::from_ratio(Ratio::from_integer(rhs));
+        *self = self.map(|el| *el * rhs);
     }
 }
 
-impl PartialOrd for M {
+impl<T: Scalar> PartialOrd for M<T> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        match self.M.partial_cmp(&other.M) {
-            Some(std::cmp::Ordering::Equal) => { 
-                self.constant.partial_cmp(&other.constant)
-            },
-            Some(ord) => {
-                Some(ord)
-            }
-            None => {
-                None
-            }
-        }
+        Some(self.cmp(other))
     }
 }
 
-impl Ord for M {
+impl<T: Scalar> Ord for M<T> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        match self.M.cmp(&other.M) {
-            std::cmp::Ordering::Equal => {
-                self.constant.cmp(&other.constant)
-            },
-            ord => {
-                ord
-            }
-        }
+        let powers: BTreeSet<&Vec<usize>> = self.terms.keys().chain(other.terms.keys()).collect();
+        let zero = T::zero();
+        powers.into_iter()
+              .rev()
+              .map(|powers| self.terms.get(powers).unwrap_or(&zero).cmp(other.terms.get(powers).unwrap_or(&zero)))
+              .find(|ord| ord != &std::cmp::Ordering::Equal)
+              .unwrap_or(std::cmp::Ordering::Equal)
     }
 }
 
-impl PartialEq for M {
+impl<T: Scalar> PartialEq for M<T> {
     fn eq(&self, other: &Self) -> bool {
-        self.M == other.M && self.constant == other.constant
+        self.terms == other.terms
     }
 }
 
-impl std::fmt::Display for M {
+// Names a monomial such as M, M^2, M1M2 or M2^3, numbering the levels only when more than one is in use.
+pub fn monomial_name(powers: &[usize], numbered: bool) -> String {
+    powers.iter()
+          .enumerate()
+          .filter(|(_, &power)| power > 0)
+          .map(|(level, &power)| {
+              let symbol = if numbered { format!("M{}", level + 1) } else { String::from("M") };
+              if power == 1 { symbol } else { format!("{}^{}", symbol, power) }
+          })
+          .collect()
+}
+
+impl<T: Scalar> std::fmt::Display for M<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match self.M == Ratio::from_integer(0i64) {
-            true => {
-                write!(f, "{}", self.constant)
-            }, 
-            false => {
-                match self.constant {
-                    c if c > Ratio::from_integer(0i64) => {
-                        if self.M == Ratio::from_integer(1i64) {
-                            write!(f, "M+{}", self.constant)
-                        } else if self.M == Ratio::from_integer(-1i64) {
-                            write!(f, "-M+{}", self.constant)
-                        } else {
-                            write!(f, "{}M+{}", self.M, self.constant)
-                        }
-                    },
-                    c if c < Ratio::from_integer(0i64) => {
-                        if self.M == Ratio::from_integer(1i64) {
-                            write!(f, "M{}", self.constant)
-                        } else if self.M == Ratio::from_integer(-1i64) {
-                            write!(f, "-M{}", self.M)
-                        } else {
-                            write!(f, "{}M{}", self.M, self.constant)
-                        }
-                    },
-                    _ => {
-                        if self.M == Ratio::from_integer(1i64) {
-                            write!(f, "M")
-                        } else if self.M == Ratio::from_integer(-1i64) {
-                            write!(f, "-M")
-                        } else {
-                            write!(f, "{}M", self.M)
-                        }
-                    },
-                }
-            },
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let numbered = self.levels() > 1;
+        for (index, (powers, coefficient)) in self.terms.iter().rev().enumerate() {
+            if index > 0 && coefficient > &T::zero() {
+                write!(f, "+")?;
+            }
+            if powers.is_empty() {
+                write!(f, "{}", coefficient)?;
+            } else if coefficient == &T::one() {
+                write!(f, "{}", monomial_name(powers, numbered))?;
+            } else if coefficient == &-T::one() {
+                write!(f, "-{}", monomial_name(powers, numbered))?;
+            } else {
+                write!(f, "{}{}", coefficient, monomial_name(powers, numbered))?;
+            }
         }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::M;
+    use num::rational::Ratio;
+
+    fn r(numer: i64, denom: i64) -> Ratio<i64> {
+        Ratio::new(numer, denom)
+    }
+
+    fn m(M: i64, constant: i64) -> M {
+        M::new(r(M, 1), r(constant, 1))
+    }
+
+    fn poly(terms: &[i64]) -> M {
+        M::from_terms(terms.iter().map(|&el| r(el, 1)).collect())
+    }
+
+    #[test]
+    fn trims_trailing_zero_terms() {
+        assert_eq!(M::from_terms(vec![r(3, 1), r(0, 1), r(0, 1)]).monomials().collect::<Vec<_>>(), vec![(&Vec::new(), &r(3, 1))]);
+        assert_eq!(M::from_monomials(vec![(vec![1, 0, 0], r(2, 1))]), m(2, 0));
+        assert!(m(0, 0).is_zero());
+        assert_eq!(m(0, 0).degree(), 0);
+        assert_eq!(m(2, 0).degree(), 1);
+        assert_eq!(poly(&[1, 2, 3]).degree(), 2);
+        assert_eq!(m(0, 5), poly(&[5, 0]));
+    }
+
+    #[test]
+    fn reads_terms() {
+        let value = M::new(r(2, 3), r(-1, 2));
+        assert_eq!(value.m(), r(2, 3));
+        assert_eq!(value.constant(), r(-1, 2));
+        assert_eq!(value.term(2), r(0, 1));
+        assert_eq!(poly(&[1, 2, 3]).term(2), r(3, 1));
+    }
+
+    #[test]
+    fn adds_and_subtracts() {
+        let mut value = m(1, 2);
+        value += &m(-3, 5);
+        assert_eq!(value, m(-2, 7));
+        value -= &m(-2, 7);
+        assert!(value.is_zero());
+        assert_eq!(&m(1, 2) - m(1, 2), m(0, 0));
+        assert_eq!(&poly(&[1, 2, 3]) - m(2, 1), poly(&[0, 0, 3]));
+        let total: M = vec![m(1, 1), m(2, -3), poly(&[0, 0, 1])].into_iter().sum();
+        assert_eq!(total, poly(&[-2, 3, 1]));
+        let empty: M = Vec::new().into_iter().sum();
+        assert!(empty.is_zero());
+    }
+
+    #[test]
+    fn negates() {
+        assert_eq!(-&m(1, -2), m(-1, 2));
+        assert_eq!(-&poly(&[0, 0, 4]), poly(&[0, 0, -4]));
+        assert!((-&m(0, 0)).is_zero());
+    }
+
+    #[test]
+    fn multiplies_by_ratios() {
+        assert_eq!(&r(1, 2) * &m(4, -6), m(2, -3));
+        assert!((&r(0, 1) * &m(4, -6)).is_zero());
+        let mut value = m(1, -2);
+        value *= -1;
+        assert_eq!(value, m(-1, 2));
+        value *= 0;
+        assert!(value.is_zero());
+    }
+
+    #[test]
+    fn multiplies_constants_and_m() {
+        assert_eq!(&m(0, 3) * &m(0, 4), m(0, 12));
+        assert_eq!(&m(0, 3) * &m(2, 1), m(6, 3));
+        assert_eq!(&m(2, 1) * &m(0, 3), m(6, 3));
+        assert!((&m(0, 0) * &m(2, 1)).is_zero());
+    }
+
+    #[test]
+    fn multiplies_two_m_values_into_a_square() {
+        // (M + 1)(M - 1) = M^2 - 1
+        assert_eq!(&m(1, 1) * &m(1, -1), poly(&[-1, 0, 1]));
+        // (2M + 3)(-M + 1/2) = -2M^2 - 2M + 3/2
+        assert_eq!(&m(2, 3) * &M::new(r(-1, 1), r(1, 2)), M::from_terms(vec![r(3, 2), r(-2, 1), r(-2, 1)]));
+        assert_eq!(&m(1, 0) * &m(1, 0), poly(&[0, 0, 1]));
+        assert_eq!(&poly(&[0, 0, 1]) * &m(1, 0), poly(&[0, 0, 0, 1]));
+    }
+
+    #[test]
+    fn divides_by_ratios() {
+        assert_eq!(&m(2, -3) / &r(2, 1), M::new(r(1, 1), r(-3, 2)));
+        assert_eq!(poly(&[2, 4, 6]) / &r(-2, 1), poly(&[-1, -2, -3]));
+    }
+
+    #[test]
+    fn orders_by_highest_power_first() {
+        assert!(m(1, -1000) > m(0, 1000));
+        assert!(m(-1, 1000) < m(0, -1000));
+        assert!(m(2, 0) > m(1, 1000));
+        assert!(m(1, 2) > m(1, 1));
+        assert!(poly(&[0, -1000, 1]) > m(1000, 1000));
+        assert!(poly(&[0, 0, -1]) < m(-1000, -1000));
+        assert_eq!(m(1, 2).cmp(&m(1, 2)), std::cmp::Ordering::Equal);
+        assert_eq!(vec![m(0, 3), m(-1, 0), poly(&[0, 0, 1]), m(1, -5)].into_iter().max(), Some(poly(&[0, 0, 1])));
+    }
+
+    #[test]
+    fn formats_constants() {
+        assert_eq!(m(0, 0).to_string(), "0");
+        assert_eq!(m(0, 3).to_string(), "3");
+        assert_eq!(m(0, -3).to_string(), "-3");
+        assert_eq!(M::new(r(0, 1), r(1, 2)).to_string(), "1/2");
+    }
+
+    #[test]
+    fn formats_m_with_every_constant_sign() {
+        assert_eq!(m(1, 0).to_string(), "M");
+        assert_eq!(m(1, 3).to_string(), "M+3");
+        assert_eq!(m(1, -3).to_string(), "M-3");
+        assert_eq!(m(-1, 0).to_string(), "-M");
+        assert_eq!(m(-1, 3).to_string(), "-M+3");
+        assert_eq!(m(-1, -3).to_string(), "-M-3");
+        assert_eq!(m(2, 0).to_string(), "2M");
+        assert_eq!(m(2, 3).to_string(), "2M+3");
+        assert_eq!(m(2, -3).to_string(), "2M-3");
+        assert_eq!(m(-2, 0).to_string(), "-2M");
+        assert_eq!(m(-2, 3).to_string(), "-2M+3");
+        assert_eq!(m(-2, -3).to_string(), "-2M-3");
+        assert_eq!(M::new(r(1, 2), r(-1, 3)).to_string(), "1/2M-1/3");
+    }
+
+    #[test]
+    fn formats_higher_powers() {
+        assert_eq!(poly(&[0, 0, 1]).to_string(), "M^2");
+        assert_eq!(poly(&[-1, 0, 1]).to_string(), "M^2-1");
+        assert_eq!(poly(&[3, -1, -2]).to_string(), "-2M^2-M+3");
+        assert_eq!(poly(&[0, 1, 0, -1]).to_string(), "-M^3+M");
+    }
+
+    fn levels(constant: i64, levels: &[i64]) -> M {
+        M::from_levels(r(constant, 1), levels.iter().map(|&el| r(el, 1)).collect())
+    }
+
+    #[test]
+    fn reads_levels() {
+        let value = levels(3, &[1, -2, 0]);
+        assert_eq!(value.levels(), 2);
+        assert_eq!(value.level(1), r(1, 1));
+        assert_eq!(value.level(2), r(-2, 1));
+        assert_eq!(value.level(3), r(0, 1));
+        assert_eq!(value.level(0), r(0, 1));
+        assert_eq!(value.m(), value.level(1));
+        assert_eq!(value.constant(), r(3, 1));
+        assert_eq!(m(2, 1).levels(), 1);
+        assert_eq!(m(0, 1).levels(), 0);
+    }
+
+    #[test]
+    fn adds_levels_separately() {
+        let mut value = levels(1, &[1, 2]);
+        value += &levels(-1, &[0, -2, 5]);
+        assert_eq!(value, levels(0, &[1, 0, 5]));
+        assert_eq!(&value - levels(0, &[1, 0, 5]), m(0, 0));
+    }
+
+    #[test]
+    fn multiplies_levels() {
+        // (M1 + M2)(M1 - M2) = M1^2 - M2^2
+        let product = &levels(0, &[1, 1]) * &levels(0, &[1, -1]);
+        assert_eq!(product, M::from_monomials(vec![(vec![2], r(1, 1)), (vec![0, 2], r(-1, 1))]));
+        assert_eq!(product.degree(), 2);
+        assert_eq!(&levels(0, &[1]) * &levels(0, &[0, 1]), M::from_monomials(vec![(vec![1, 1], r(1, 1))]));
+    }
+
+    #[test]
+    fn orders_levels_lexicographically() {
+        assert!(levels(0, &[1, -1000]) > levels(1000, &[0, 1000]));
+        assert!(levels(0, &[0, 1]) > m(0, 1000));
+        assert!(levels(0, &[0, -1]) < m(0, -1000));
+        assert!(levels(0, &[0, 1000]) < m(1, 0));
+        assert!(levels(0, &[0, 0, 1]) < levels(0, &[0, 1]));
+        assert!(M::from_monomials(vec![(vec![0, 2], r(1, 1))]) < m(1, 0));
+        assert!(M::from_monomials(vec![(vec![1, 1], r(1, 1))]) > m(1000, 0));
+        assert!(M::from_monomials(vec![(vec![1, 1], r(1, 1))]) < poly(&[0, 0, 1]));
+    }
+
+    #[test]
+    fn formats_levels() {
+        assert_eq!(levels(0, &[1, 1]).to_string(), "M1+M2");
+        assert_eq!(levels(3, &[2, -1]).to_string(), "2M1-M2+3");
+        assert_eq!(levels(-1, &[0, 1, -1]).to_string(), "M2-M3-1");
+        assert_eq!(levels(0, &[0, 1]).to_string(), "M2");
+        assert_eq!(M::from_monomials(vec![(vec![1, 1], r(-2, 1)), (vec![0, 2], r(1, 2))]).to_string(), "-2M1M2+1/2M2^2");
+    }
+
+    #[test]
+    fn evaluates_at_a_number() {
+        assert_eq!(m(2, -3).evaluate(&r(1000, 1)), r(1997, 1));
+        assert_eq!(poly(&[1, 0, -1]).evaluate(&r(1, 2)), r(3, 4));
+        assert_eq!(m(0, 0).evaluate(&r(1000, 1)), r(0, 1));
+        assert_eq!(levels(1, &[1, 1]).evaluate(&r(10, 1)), r(21, 1));
+    }
+
+    #[test]
+    fn serializes_terms() {
+        let value = levels(3, &[1, -1]);
+        let json = serde_json::to_value(&value).unwrap();
+        assert_eq!(json, serde_json::json!([
+            { "powers": [], "numerator": 3, "denominator": 1 },
+            { "powers": [0, 1], "numerator": -1, "denominator": 1 },
+            { "powers": [1], "numerator": 1, "denominator": 1 },
+        ]));
+        assert_eq!(serde_json::from_value::<M>(json).unwrap(), value);
+        let parsed: M = serde_json::from_str(r#"[{ "powers": [1, 0], "numerator": 2, "denominator": 4 }, { "powers": [1], "numerator": 1, "denominator": 2 }]"#).unwrap();
+        assert_eq!(parsed, m(1, 0));
+        assert!(serde_json::from_str::<M>(r#"[{ "powers": [], "numerator": 1, "denominator": 0 }]"#).is_err());
     }
 }
\ No newline at end of file
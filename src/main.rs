@@ -3,34 +3,211 @@
 #[macro_use] 
 extern crate rocket;
 
-mod m;
-mod tableau_data;
-mod tableau;
-
-use tableau_data::TableauData;
-use tableau::Tableau;
+use simplex_solver::{csv, latex, lp, mps, plot, polytope, report, solver};
+use simplex_solver::compact::CompactTableauData;
+use simplex_solver::enumeration::EnumerationData;
+use simplex_solver::pivot_feedback::PivotFeedback;
+use simplex_solver::problem::Problem;
+use simplex_solver::session::{SessionStore, SessionData, PivotData};
+use simplex_solver::tableau_data::TableauData;
+use simplex_solver::tableau::Tableau;
+use simplex_solver::verification::{VerificationRequest, VerificationData};
+use rocket::State;
+use rocket::http::ContentType;
+use rocket::response::{content, status};
 use rocket_contrib::json::Json;
 
-#[post("/solve", format = "json", data = "<tableau>")]
-fn solve(tableau: Json<TableauData>) -> Json<TableauData> {
-    let mut t = Tableau::new(tableau.0);
+#[derive(Responder)]
+enum SolveResponse {
+    Json(Json<TableauData>),
+    Text(content::Plain<String>),
+    Html(content::Html<String>),
+    Content(content::Content<String>),
+    Polytope(Json<polytope::PolytopeData>),
+    Compact(Json<CompactTableauData>),
+}
+
+#[post("/solve?<format>", format = "json", data = "<tableau>")]
+fn solve(tableau: Json<TableauData>, format: Option<String>) -> SolveResponse {
+    solve_data(tableau.into_inner(), format)
+}
+
+// Solves a problem sent as a model file instead of JSON. input names the file format, and since a model file has no
+// place for the solver options they are given in the query instead.
+#[allow(non_snake_case)]
+#[post("/solve?<input>&<format>&<solve_algorithm>&<big_M_solve_algorithm>&<entering_rule>&<leaving_rule>", data = "<text>", rank = 2)]
+fn solve_text(text: String, input: String, format: Option<String>, solve_algorithm: Option<String>, big_M_solve_algorithm: Option<String>,
+              entering_rule: Option<String>, leaving_rule: Option<String>) -> SolveResponse {
+    let problem = match input.as_str() {
+        "mps" => mps::read_mps(&text, false),
+        "fixed-mps" => mps::read_mps(&text, true),
+        "lp" => lp::read_lp(&text),
+        other => Err(format!("Unknown input format '{}'. Supported input formats are: mps, fixed-mps, lp.", other)),
+    };
+    let mut data = match problem {
+        Ok(problem) => problem.to_tableau_data(),
+        Err(message) => return SolveResponse::Json(Json(TableauData::error(message))),
+    };
+    if let Some(solve_algorithm) = solve_algorithm {
+        data.solve_algorithm = solve_algorithm;
+    }
+    if let Some(big_M_solve_algorithm) = big_M_solve_algorithm {
+        data.big_M_solve_algorithm = big_M_solve_algorithm;
+    }
+    if let Some(entering_rule) = entering_rule {
+        data.entering_rule = entering_rule;
+    }
+    if let Some(leaving_rule) = leaving_rule {
+        data.leaving_rule = leaving_rule;
+    }
+    solve_data(data, format)
+}
+
+fn solve_data(data: TableauData, format: Option<String>) -> SolveResponse {
+    let (mut t, cycled) = match solver::solve(data, true) {
+        Ok(solved) => solved,
+        Err(message) => return SolveResponse::Json(Json(TableauData::error(message))),
+    };
+    match format.as_deref() {
+        None | Some("json") => {
+            if t.error && !cycled {
+                SolveResponse::Json(Json(TableauData::new(Tableau::error_tableau(t))))
+            } else {
+                SolveResponse::Json(Json(TableauData::new(t)))
+            }
+        },
+        Some("compact") => {
+            if t.error && !cycled {
+                SolveResponse::Compact(Json(CompactTableauData::new(Tableau::error_tableau(t))))
+            } else {
+                SolveResponse::Compact(Json(CompactTableauData::new(t)))
+            }
+        },
+        Some("latex") => SolveResponse::Text(content::Plain(latex::tableau_to_latex(&t))),
+        Some("html") => SolveResponse::Html(content::Html(report::tableau_to_html(&t))),
+        Some("markdown") => SolveResponse::Content(content::Content(ContentType::new("text", "markdown"), report::tableau_to_markdown(&t))),
+        Some("svg") => match plot::tableau_to_svg(&t) {
+            Ok(svg) => SolveResponse::Content(content::Content(ContentType::SVG, svg)),
+            Err(message) => {
+                t.error = true;
+                t.error_message = message;
+                SolveResponse::Json(Json(TableauData::new(Tableau::error_tableau(t))))
+            }
+        },
+        Some("polytope") => match polytope::tableau_to_polytope(&t) {
+            Ok(polytope) => SolveResponse::Polytope(Json(polytope)),
+            Err(message) => {
+                t.error = true;
+                t.error_message = message;
+                SolveResponse::Json(Json(TableauData::new(Tableau::error_tableau(t))))
+            }
+        },
+        Some(other) => {
+            t.error = true;
+            t.error_message = format!("Unknown output format '{}'.", other);
+            SolveResponse::Json(Json(TableauData::new(Tableau::error_tableau(t))))
+        }
+    }
+}
+
+#[post("/sessions", format = "json", data = "<tableau>")]
+fn create_session(tableau: Json<TableauData>, sessions: State<SessionStore>) -> Json<SessionData> {
+    let mut t: Tableau = Tableau::new(tableau.0);
     t.setup();
-    t.solve();
-    if t.has_artificial_vars && t.big_M_solve_algorithm.as_str() == "two-phase" {
-        t.two_phase_second_phase();
+    if t.error {
+        return Json(SessionData { id: None, tableau: TableauData::new(Tableau::error_tableau(t)) });
+    }
+    let id = sessions.create(t.clone());
+    Json(SessionData { id: Some(id), tableau: TableauData::new(t) })
+}
+
+#[get("/sessions/<id>")]
+fn get_session(id: u64, sessions: State<SessionStore>) -> Option<Json<SessionData>> {
+    sessions.with_session(id, |t| Json(SessionData { id: Some(id), tableau: TableauData::new(t.clone()) }))
+}
+
+#[post("/sessions/<id>/pivot", format = "json", data = "<pivot>")]
+fn pivot_session(id: u64, pivot: Json<PivotData>, sessions: State<SessionStore>) -> Option<Json<SessionData>> {
+    sessions.with_session(id, |t| {
+        let mut next = t.clone();
+        next.apply_pivot(pivot.entering_index, pivot.leaving_index);
+        if next.error {
+            return Json(SessionData { id: Some(id), tableau: TableauData::new(Tableau::error_tableau(next)) });
+        }
+        *t = next.clone();
+        Json(SessionData { id: Some(id), tableau: TableauData::new(next) })
+    })
+}
+
+#[post("/sessions/<id>/check", format = "json", data = "<pivot>")]
+fn check_session_pivot(id: u64, pivot: Json<PivotData>, sessions: State<SessionStore>) -> Option<Json<PivotFeedback>> {
+    sessions.with_session(id, |t| Json(t.check_pivot(pivot.entering_index, pivot.leaving_index)))
+}
+
+#[post("/verify", format = "json", data = "<request>")]
+fn verify(request: Json<VerificationRequest>) -> Json<VerificationData> {
+    let request = request.into_inner();
+    let previous = match Tableau::restore(request.previous) {
+        Ok(t) => t,
+        Err(message) => return Json(VerificationData::error(format!("Previous tableau: {}", message))),
+    };
+    let next = match Tableau::restore(request.next) {
+        Ok(t) => t,
+        Err(message) => return Json(VerificationData::error(format!("Next tableau: {}", message))),
+    };
+    Json(previous.verify_pivot(&next))
+}
+
+#[post("/enumerate", format = "json", data = "<tableau>")]
+fn enumerate(tableau: Json<TableauData>) -> Json<EnumerationData> {
+    let t: Tableau = Tableau::new(tableau.0);
+    if t.error {
+        return Json(EnumerationData::error(t.error_message));
+    }
+    match t.enumerate_vertices() {
+        Ok(enumeration) => Json(EnumerationData::new(enumeration)),
+        Err(message) => Json(EnumerationData::error(message)),
     }
-    if t.solved {
-        t.get_solution();
-        Json(TableauData::new(t))
-    } else if t.error {
-        Json(TableauData::new(Tableau::error_tableau(t)))
-    } else {
-        t.error = true; 
-        t.error_message = String::from("Stuck in a cycle, terminted solution process.");
-        Json(TableauData::new(t))
+}
+
+// Reads a tableau from a spreadsheet saved as CSV and returns it as a problem for /solve. delimiter is a single
+// character and defaults to a comma, since some spreadsheets save with semicolons instead.
+#[post("/import/csv?<objective_row>&<header>&<delimiter>", data = "<text>")]
+fn import_csv(text: String, objective_row: Option<usize>, header: Option<bool>, delimiter: Option<String>) -> Json<TableauData> {
+    let mut chars = delimiter.as_deref().unwrap_or(",").chars();
+    let delimiter = match (chars.next(), chars.next()) {
+        (Some(delimiter), None) => delimiter,
+        _ => return Json(TableauData::error(String::from("The delimiter has to be a single character."))),
+    };
+    match csv::read_csv(&text, objective_row, header.unwrap_or(false), delimiter) {
+        Ok(data) => Json(data),
+        Err(message) => Json(TableauData::error(message)),
     }
 }
 
+// Writes a problem as an MPS file. The problem is read as the tableau would solve it, so every row is an equality
+// and every variable is non-negative. style=fixed writes fixed MPS, anything else free MPS.
+#[post("/export/mps?<style>", format = "json", data = "<tableau>")]
+fn export_mps(tableau: Json<TableauData>, style: Option<String>) -> Result<content::Plain<String>, status::BadRequest<String>> {
+    let fixed = style.as_deref() == Some("fixed");
+    Problem::from_tableau_data(tableau.into_inner())
+        .and_then(|problem| mps::write_mps(&problem, fixed))
+        .map(content::Plain)
+        .map_err(|message| status::BadRequest(Some(message)))
+}
+
+// Writes a problem in CPLEX LP format, read the same way as for /export/mps.
+#[post("/export/lp", format = "json", data = "<tableau>")]
+fn export_lp(tableau: Json<TableauData>) -> Result<content::Plain<String>, status::BadRequest<String>> {
+    Problem::from_tableau_data(tableau.into_inner())
+        .and_then(|problem| lp::write_lp(&problem))
+        .map(content::Plain)
+        .map_err(|message| status::BadRequest(Some(message)))
+}
+
 fn main() {
-    rocket::ignite().mount("/", routes![solve]).launch();
+    rocket::ignite()
+        .manage(SessionStore::new())
+        .mount("/", routes![solve, create_session, get_session, pivot_session, check_session_pivot, verify, enumerate, solve_text, export_mps, export_lp, import_csv])
+        .launch();
 }
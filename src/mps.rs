@@ -0,0 +1,473 @@
+use crate::geometry::Relation;
+use crate::parse::parse_ratio;
+use crate::problem::{Problem, format_decimal};
+
+use num::rational::Ratio;
+use num::{Signed, Zero};
+
+// Fixed MPS puts each field in set columns: 2-3, 5-12, 15-22, 25-36, 40-47 and 50-61.
+const FIXED_FIELDS: [(usize, usize); 6] = [(1, 3), (4, 12), (14, 22), (24, 36), (39, 47), (49, 61)];
+
+#[derive(PartialEq)]
+enum Section {
+    Name,
+    ObjSense,
+    Rows,
+    Columns,
+    Rhs,
+    Ranges,
+    Bounds,
+    End,
+}
+
+fn fields(line: &str, fixed: bool) -> Vec<String> {
+    if !fixed {
+        return line.split_whitespace().map(String::from).collect();
+    }
+    let chars: Vec<char> = line.chars().collect();
+    FIXED_FIELDS.iter()
+                .map(|&(start, end)| chars.get(start.min(chars.len())..end.min(chars.len()))
+                                          .map(|field| field.iter().collect::<String>().trim().to_string())
+                                          .unwrap_or_default())
+                .filter(|field| !field.is_empty())
+                .collect()
+}
+
+// The RHS and RANGES sections name a set first, which some files leave out. An even number of fields means there
+// is no set name, since the rest of the line is made of row and value pairs.
+fn split_set_name(fields: &[String]) -> (Option<&str>, &[String]) {
+    if fields.len() % 2 == 1 {
+        (Some(fields[0].as_str()), &fields[1..])
+    } else {
+        (None, fields)
+    }
+}
+
+fn same_set(used: &mut Option<String>, name: Option<&str>) -> bool {
+    let name = name.unwrap_or("");
+    match used {
+        Some(used) => used.as_str() == name,
+        None => {
+            *used = Some(String::from(name));
+            true
+        }
+    }
+}
+
+// Reads a problem in fixed or free MPS format. Only the first RHS, RANGES and BOUNDS set is used, integer markers
+// and integer bound types are kept on the variables, and the problem is a minimization unless OBJSENSE says MAX.
+pub fn read_mps(text: &str, fixed: bool) -> Result<Problem, String> {
+    let mut problem = Problem::new("");
+    let mut section = Section::Name;
+    let mut has_objective = false;
+    let mut other_objectives: Vec<String> = Vec::new();
+    let mut integer_marker = false;
+    let mut rhs_set: Option<String> = None;
+    let mut range_set: Option<String> = None;
+    let mut bound_set: Option<String> = None;
+    let mut ranges: Vec<(usize, Ratio<i64>)> = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let error = |message: String| format!("Line {}: {}", number + 1, message);
+        if line.trim().is_empty() || line.starts_with('*') {
+            continue;
+        }
+        if !line.starts_with(|ch: char| ch.is_whitespace()) {
+            let mut words = line.split_whitespace();
+            let header = words.next().unwrap_or("").to_uppercase();
+            let rest: Vec<&str> = words.collect();
+            section = match header.as_str() {
+                "NAME" => {
+                    problem.name = line[4..].trim().to_string();
+                    Section::Name
+                },
+                "OBJSENSE" => {
+                    if let Some(sense) = rest.first() {
+                        problem.maximize = read_sense(sense).ok_or_else(|| error(format!("Unknown objective sense '{}'.", sense)))?;
+                    }
+                    Section::ObjSense
+                },
+                "ROWS" => Section::Rows,
+                "COLUMNS" => Section::Columns,
+                "RHS" => Section::Rhs,
+                "RANGES" => Section::Ranges,
+                "BOUNDS" => Section::Bounds,
+                "ENDATA" => Section::End,
+                _ => return Err(error(format!("Unsupported section '{}'.", header))),
+            };
+            if section == Section::End {
+                break;
+            }
+            continue;
+        }
+        let fields = fields(line, fixed);
+        let value = |text: &str| parse_ratio(text).map_err(&error);
+        match section {
+            Section::Name => return Err(error(String::from("Expected a section such as ROWS."))),
+            Section::ObjSense => {
+                let sense = fields.first().map(|sense| sense.as_str()).unwrap_or("");
+                problem.maximize = read_sense(sense).ok_or_else(|| error(format!("Unknown objective sense '{}'.", sense)))?;
+            },
+            Section::Rows => {
+                let (kind, name) = match fields.as_slice() {
+                    [kind, name] => (kind.to_uppercase(), name.as_str()),
+                    _ => return Err(error(String::from("A row needs a type and a name."))),
+                };
+                if problem.find_constraint(name).is_some() || problem.objective_name == name && has_objective {
+                    return Err(error(format!("Row '{}' is declared twice.", name)));
+                }
+                match kind.as_str() {
+                    "N" if !has_objective => {
+                        problem.objective_name = String::from(name);
+                        has_objective = true;
+                    },
+                    "N" => other_objectives.push(String::from(name)),
+                    "L" => { problem.add_constraint(name, Relation::LessEqual, Ratio::zero()); },
+                    "G" => { problem.add_constraint(name, Relation::GreaterEqual, Ratio::zero()); },
+                    "E" => { problem.add_constraint(name, Relation::Equal, Ratio::zero()); },
+                    _ => return Err(error(format!("Unknown row type '{}'.", kind))),
+                }
+            },
+            Section::Columns => {
+                if fields.len() >= 3 && fields[1].trim_matches('\'').eq_ignore_ascii_case("MARKER") {
+                    match fields[2].trim_matches('\'').to_uppercase().as_str() {
+                        "INTORG" => integer_marker = true,
+                        "INTEND" => integer_marker = false,
+                        other => return Err(error(format!("Unknown marker '{}'.", other))),
+                    }
+                    continue;
+                }
+                if fields.len() != 3 && fields.len() != 5 {
+                    return Err(error(String::from("A column entry needs a column name and one or two row and value pairs.")));
+                }
+                let col = problem.variable_index(&fields[0]);
+                problem.variables[col].integer |= integer_marker;
+                for pair in fields[1..].chunks(2) {
+                    let coefficient = value(&pair[1])?;
+                    if has_objective && pair[0] == problem.objective_name {
+                        problem.objective[col] = coefficient;
+                    } else if let Some(row) = problem.find_constraint(&pair[0]) {
+                        problem.constraints[row].coefficients[col] = coefficient;
+                    } else if !other_objectives.contains(&pair[0]) {
+                        return Err(error(format!("Unknown row '{}'.", pair[0])));
+                    }
+                }
+            },
+            Section::Rhs | Section::Ranges => {
+                let (set, pairs) = split_set_name(&fields);
+                if pairs.is_empty() || pairs.len() > 4 {
+                    return Err(error(String::from("An entry needs one or two row and value pairs.")));
+                }
+                let used = if section == Section::Rhs { &mut rhs_set } else { &mut range_set };
+                if !same_set(used, set) {
+                    continue;
+                }
+                for pair in pairs.chunks(2) {
+                    let amount = value(&pair[1])?;
+                    if has_objective && pair[0] == problem.objective_name {
+                        return Err(error(String::from("A constant in the objective is not supported.")));
+                    }
+                    match problem.find_constraint(&pair[0]) {
+                        Some(row) if section == Section::Rhs => problem.constraints[row].rhs = amount,
+                        Some(row) => ranges.push((row, amount)),
+                        None if other_objectives.contains(&pair[0]) => {},
+                        None => return Err(error(format!("Unknown row '{}'.", pair[0]))),
+                    }
+                }
+            },
+            Section::Bounds => {
+                let kind = fields.first().map(|kind| kind.to_uppercase()).unwrap_or_default();
+                let needs_value = matches!(kind.as_str(), "UP" | "LO" | "FX" | "LI" | "UI");
+                let (set, name, amount) = match (fields.len(), needs_value) {
+                    (4, true) => (Some(fields[1].as_str()), &fields[2], Some(value(&fields[3])?)),
+                    (3, true) => (None, &fields[1], Some(value(&fields[2])?)),
+                    (3, false) | (4, false) => (Some(fields[1].as_str()), &fields[2], None),
+                    (2, false) => (None, &fields[1], None),
+                    _ => return Err(error(String::from("A bound needs a type, a column and, for most types, a value."))),
+                };
+                if !same_set(&mut bound_set, set) {
+                    continue;
+                }
+                let col = problem.find_variable(name).ok_or_else(|| error(format!("Unknown column '{}'.", name)))?;
+                let variable = &mut problem.variables[col];
+                match (kind.as_str(), amount) {
+                    ("UP", Some(upper)) | ("UI", Some(upper)) => {
+                        if upper.is_negative() && variable.lower == Some(Ratio::zero()) {
+                            variable.lower = None;
+                        }
+                        variable.upper = Some(upper);
+                    },
+                    ("LO", Some(lower)) | ("LI", Some(lower)) => variable.lower = Some(lower),
+                    ("FX", Some(fixed)) => {
+                        variable.lower = Some(fixed);
+                        variable.upper = Some(fixed);
+                    },
+                    ("FR", _) => {
+                        variable.lower = None;
+                        variable.upper = None;
+                    },
+                    ("MI", _) => variable.lower = None,
+                    ("PL", _) => variable.upper = None,
+                    ("BV", _) => {
+                        variable.lower = Some(Ratio::zero());
+                        variable.upper = Some(Ratio::from_integer(1));
+                    },
+                    _ => return Err(error(format!("Unknown bound type '{}'.", kind))),
+                }
+                variable.integer |= matches!(kind.as_str(), "LI" | "UI" | "BV");
+            },
+            Section::End => break,
+        }
+    }
+    if !has_objective {
+        return Err(String::from("The ROWS section needs an N row for the objective."));
+    }
+    // A range R turns a row into lower <= ax <= upper. The row keeps one side and a new row is added for the other.
+    for (row, range) in ranges {
+        let constraint = problem.constraints[row].clone();
+        let (relation, rhs) = match constraint.relation {
+            Relation::LessEqual => (Relation::GreaterEqual, constraint.rhs - range.abs()),
+            Relation::GreaterEqual => (Relation::LessEqual, constraint.rhs + range.abs()),
+            Relation::Equal if range.is_negative() => {
+                problem.constraints[row].relation = Relation::LessEqual;
+                (Relation::GreaterEqual, constraint.rhs + range)
+            },
+            Relation::Equal => {
+                problem.constraints[row].relation = Relation::GreaterEqual;
+                (Relation::LessEqual, constraint.rhs + range)
+            },
+        };
+        let index = problem.add_constraint(&format!("{}_range", constraint.label), relation, rhs);
+        problem.constraints[index].coefficients = constraint.coefficients;
+    }
+    Ok(problem)
+}
+
+fn read_sense(text: &str) -> Option<bool> {
+    match text.to_uppercase().as_str() {
+        "MAX" | "MAXIMIZE" => Some(true),
+        "MIN" | "MINIMIZE" => Some(false),
+        _ => None,
+    }
+}
+
+// Lays a line out in the fixed MPS columns, which free MPS readers accept as well. Long names only fit in free MPS.
+fn line(fields: [&str; 6], fixed: bool) -> Result<String, String> {
+    let mut text = String::new();
+    for (&(start, end), field) in FIXED_FIELDS.iter().zip(fields.iter()) {
+        if field.is_empty() {
+            continue;
+        }
+        if fixed && (field.chars().count() > end - start || field.contains(' ')) {
+            return Err(format!("'{}' does not fit in fixed MPS, which allows {} characters without spaces here. Use free MPS instead.", field, end - start));
+        }
+        if !fixed && field.contains(char::is_whitespace) {
+            return Err(format!("'{}' contains a space, which free MPS does not allow.", field));
+        }
+        let len = text.chars().count();
+        if len < start {
+            text.push_str(&" ".repeat(start - len));
+        } else {
+            text.push(' ');
+        }
+        text.push_str(field);
+    }
+    Ok(text)
+}
+
+fn number(value: &Ratio<i64>, fixed: bool) -> String {
+    let text = format_decimal(value);
+    if fixed && text.len() > 12 {
+        format!("{:.5e}", *value.numer() as f64 / *value.denom() as f64)
+    } else {
+        text
+    }
+}
+
+pub fn write_mps(problem: &Problem, fixed: bool) -> Result<String, String> {
+    let name = if problem.name.is_empty() { "PROBLEM" } else { problem.name.as_str() };
+    let mut lines = vec![format!("NAME          {}", name)];
+    if problem.maximize {
+        lines.push(String::from("OBJSENSE"));
+        lines.push(line(["", "MAX", "", "", "", ""], fixed)?);
+    }
+    lines.push(String::from("ROWS"));
+    lines.push(line(["N", &problem.objective_name, "", "", "", ""], fixed)?);
+    for constraint in &problem.constraints {
+        let kind = match constraint.relation {
+            Relation::LessEqual => "L",
+            Relation::GreaterEqual => "G",
+            Relation::Equal => "E",
+        };
+        lines.push(line([kind, &constraint.label, "", "", "", ""], fixed)?);
+    }
+    lines.push(String::from("COLUMNS"));
+    let mut in_integer_block = false;
+    for (col, variable) in problem.variables.iter().enumerate() {
+        if variable.integer != in_integer_block {
+            let marker = if variable.integer { "'INTORG'" } else { "'INTEND'" };
+            lines.push(line(["", "MARKER", "'MARKER'", "", marker, ""], fixed)?);
+            in_integer_block = variable.integer;
+        }
+        let mut entries: Vec<(&str, String)> = Vec::new();
+        if !problem.objective[col].is_zero() {
+            entries.push((&problem.objective_name, number(&problem.objective[col], fixed)));
+        }
+        for constraint in &problem.constraints {
+            if !constraint.coefficients[col].is_zero() {
+                entries.push((&constraint.label, number(&constraint.coefficients[col], fixed)));
+            }
+        }
+        if entries.is_empty() {
+            entries.push((&problem.objective_name, String::from("0")));
+        }
+        for pair in entries.chunks(2) {
+            let (second_row, second_value) = pair.get(1).map(|(row, value)| (*row, value.as_str())).unwrap_or(("", ""));
+            lines.push(line(["", &variable.name, pair[0].0, &pair[0].1, second_row, second_value], fixed)?);
+        }
+    }
+    if in_integer_block {
+        lines.push(line(["", "MARKER", "'MARKER'", "", "'INTEND'", ""], fixed)?);
+    }
+    lines.push(String::from("RHS"));
+    for constraint in problem.constraints.iter().filter(|constraint| !constraint.rhs.is_zero()) {
+        lines.push(line(["", "RHS", &constraint.label, &number(&constraint.rhs, fixed), "", ""], fixed)?);
+    }
+    let mut bounds: Vec<String> = Vec::new();
+    for variable in &problem.variables {
+        let bound = |kind: &str, value: Option<&Ratio<i64>>| {
+            let value = value.map(|value| number(value, fixed)).unwrap_or_default();
+            line([kind, "BND", &variable.name, &value, "", ""], fixed)
+        };
+        match (variable.lower, variable.upper) {
+            (Some(lower), Some(upper)) if lower == upper => bounds.push(bound("FX", Some(&lower))?),
+            (None, None) => bounds.push(bound("FR", None)?),
+            (lower, upper) => {
+                match lower {
+                    None => bounds.push(bound("MI", None)?),
+                    Some(lower) if !lower.is_zero() => bounds.push(bound("LO", Some(&lower))?),
+                    Some(_) => {},
+                }
+                if let Some(upper) = upper {
+                    bounds.push(bound("UP", Some(&upper))?);
+                }
+            },
+        }
+    }
+    if !bounds.is_empty() {
+        lines.push(String::from("BOUNDS"));
+        lines.extend(bounds);
+    }
+    lines.push(String::from("ENDATA"));
+    Ok(lines.join("\n") + "\n")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{read_mps, write_mps};
+    use crate::geometry::Relation;
+    use crate::problem::{Problem, Variable};
+
+    use num::rational::Ratio;
+
+    fn r(value: i64) -> Ratio<i64> {
+        Ratio::from_integer(value)
+    }
+
+    fn row(problem: &Problem, name: &str) -> (Relation, Ratio<i64>) {
+        let constraint = &problem.constraints[problem.find_constraint(name).unwrap()];
+        (constraint.relation, constraint.rhs)
+    }
+
+    #[test]
+    fn applies_ranges_to_each_row_type() {
+        let text = "NAME RANGED\nROWS\n N obj\n L lim\n G low\n E up\n E down\nCOLUMNS\n x obj 1 lim 1\n x low 1 up 1\n x down 1\n\
+                    RHS\n rhs lim 10 low 2\n rhs up 5 down 5\nRANGES\n rng lim 4 low -3\n rng up 2 down -2\nENDATA\n";
+        let problem = read_mps(text, false).unwrap();
+        assert_eq!(row(&problem, "lim"), (Relation::LessEqual, r(10)));
+        assert_eq!(row(&problem, "lim_range"), (Relation::GreaterEqual, r(6)));
+        assert_eq!(row(&problem, "low"), (Relation::GreaterEqual, r(2)));
+        assert_eq!(row(&problem, "low_range"), (Relation::LessEqual, r(5)));
+        assert_eq!(row(&problem, "up"), (Relation::GreaterEqual, r(5)));
+        assert_eq!(row(&problem, "up_range"), (Relation::LessEqual, r(7)));
+        assert_eq!(row(&problem, "down"), (Relation::LessEqual, r(5)));
+        assert_eq!(row(&problem, "down_range"), (Relation::GreaterEqual, r(3)));
+    }
+
+    #[test]
+    fn negative_upper_bound_drops_the_default_lower_bound() {
+        let text = "NAME\nROWS\n N obj\n L c1\nCOLUMNS\n x obj 1 c1 1\n y obj 1 c1 1\nRHS\n rhs c1 4\n\
+                    BOUNDS\n UP bnd x -3\n LO bnd y -5\n UP bnd y -1\nENDATA\n";
+        let problem = read_mps(text, false).unwrap();
+        let x = &problem.variables[problem.find_variable("x").unwrap()];
+        assert_eq!((x.lower, x.upper), (None, Some(r(-3))));
+        let y = &problem.variables[problem.find_variable("y").unwrap()];
+        assert_eq!((y.lower, y.upper), (Some(r(-5)), Some(r(-1))));
+    }
+
+    #[test]
+    fn marks_columns_between_integer_markers() {
+        let text = "NAME\nROWS\n N obj\n L c1\nCOLUMNS\n MARKER 'MARKER' 'INTORG'\n x obj 1 c1 1\n MARKER 'MARKER' 'INTEND'\n \
+                    y obj 2 c1 1\nRHS\n rhs c1 4\nENDATA\n";
+        let problem = read_mps(text, false).unwrap();
+        let integer: Vec<bool> = problem.variables.iter().map(|variable| variable.integer).collect();
+        assert_eq!(integer, vec![true, false]);
+    }
+
+    #[test]
+    fn reads_names_with_spaces_from_fixed_columns() {
+        let text = ["NAME          FIXED",
+                    "ROWS",
+                    &format!(" {:<2} {}", "N", "COST"),
+                    &format!(" {:<2} {}", "L", "LIM 1"),
+                    "COLUMNS",
+                    &format!("    {:<8}  {:<8}  {:<12}   {:<8}  {}", "X 1", "COST", "1.5", "LIM 1", "2"),
+                    "RHS",
+                    &format!("    {:<8}  {:<8}  {}", "RHS", "LIM 1", "4"),
+                    "BOUNDS",
+                    &format!(" {:<2} {:<8}  {:<8}  {}", "FX", "BND", "X 1", "2"),
+                    "ENDATA"].join("\n");
+        let problem = read_mps(&text, true).unwrap();
+        assert_eq!(problem.name, "FIXED");
+        assert_eq!(problem.objective, vec![Ratio::new(3, 2)]);
+        assert_eq!(problem.constraints[0].label, "LIM 1");
+        assert_eq!(problem.constraints[0].coefficients, vec![r(2)]);
+        assert_eq!(row(&problem, "LIM 1"), (Relation::LessEqual, r(4)));
+        let x = &problem.variables[problem.find_variable("X 1").unwrap()];
+        assert_eq!((x.lower, x.upper), (Some(r(2)), Some(r(2))));
+    }
+
+    #[test]
+    fn reads_back_what_it_writes() {
+        let mut problem = Problem::new("TRIP");
+        problem.maximize = true;
+        for name in ["x", "y", "z", "w"] {
+            problem.variable_index(name);
+        }
+        problem.objective = vec![r(3), Ratio::new(-1, 4), r(0), r(2)];
+        let index = problem.add_constraint("c1", Relation::LessEqual, r(10));
+        problem.constraints[index].coefficients = vec![r(1), r(2), r(0), r(1)];
+        let index = problem.add_constraint("c2", Relation::GreaterEqual, r(-2));
+        problem.constraints[index].coefficients = vec![r(0), r(1), r(-1), r(0)];
+        let index = problem.add_constraint("c3", Relation::Equal, Ratio::new(1, 2));
+        problem.constraints[index].coefficients = vec![r(1), r(0), r(1), r(0)];
+        problem.variables[1] = Variable { lower: None, upper: None, ..Variable::new("y") };
+        problem.variables[2] = Variable { lower: Some(r(-1)), upper: Some(r(5)), integer: true, ..Variable::new("z") };
+        problem.variables[3] = Variable { lower: Some(r(2)), upper: Some(r(2)), ..Variable::new("w") };
+        for fixed in [false, true] {
+            let read = read_mps(&write_mps(&problem, fixed).unwrap(), fixed).unwrap();
+            assert_eq!(read.name, problem.name);
+            assert_eq!(read.maximize, problem.maximize);
+            assert_eq!(read.objective, problem.objective);
+            let names: Vec<&str> = read.variables.iter().map(|variable| variable.name.as_str()).collect();
+            assert_eq!(names, vec!["x", "y", "z", "w"]);
+            for (read, variable) in read.variables.iter().zip(problem.variables.iter()) {
+                assert_eq!((read.lower, read.upper, read.integer), (variable.lower, variable.upper, variable.integer), "{}", variable.name);
+            }
+            for (read, constraint) in read.constraints.iter().zip(problem.constraints.iter()) {
+                assert_eq!((&read.label, read.relation, read.rhs), (&constraint.label, constraint.relation, constraint.rhs));
+                assert_eq!(read.coefficients, constraint.coefficients);
+            }
+        }
+    }
+}
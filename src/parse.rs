@@ -0,0 +1,161 @@
+use crate::m::M;
+
+use num::rational::Ratio;
+use num::{CheckedDiv, Zero};
+
+// The highest level or power of M a value can be written with. Each level is a slot in every value's powers, so a
+// level such as M99999999999999 is rejected instead of allocated for.
+const MAX_M_LEVEL: usize = 64;
+
+// Reads a number written as an integer, a fraction such as "3/4", a decimal such as "0.125" or in scientific notation
+// such as "1.5e2". Decimals are read digit by digit, so the result is exact rather than the nearest float.
+pub fn parse_ratio(text: &str) -> Result<Ratio<i64>, String> {
+    let trimmed = text.trim();
+    match trimmed.split_once('/') {
+        Some((numerator, denominator)) => {
+            let numerator = parse_decimal(numerator.trim()).ok_or_else(|| not_a_number(text))?;
+            let denominator = parse_decimal(denominator.trim()).ok_or_else(|| not_a_number(text))?;
+            if denominator.is_zero() {
+                return Err(format!("'{}' has a denominator of zero.", text));
+            }
+            numerator.checked_div(&denominator).ok_or_else(|| too_large(text))
+        },
+        None => parse_decimal(trimmed).ok_or_else(|| not_a_number(text)),
+    }
+}
+
+// Reads a value that may involve M, written the way M is displayed: "2M+3/4", "-M", "M^2-1" or, with more than one
+// level, "2M1-M2+3". An unnumbered M is the first level.
+pub fn parse_m(text: &str) -> Result<M, String> {
+    let compact: String = text.chars().filter(|ch| !ch.is_whitespace()).collect();
+    if compact.is_empty() {
+        return Err(not_a_number(text));
+    }
+    let chars: Vec<char> = compact.chars().collect();
+    let mut terms: Vec<String> = Vec::new();
+    let mut term = String::new();
+    for (index, &ch) in chars.iter().enumerate() {
+        let exponent_sign = index > 0 && (chars[index - 1] == 'e' || chars[index - 1] == 'E');
+        if (ch == '+' || ch == '-') && !term.is_empty() && !exponent_sign {
+            terms.push(term);
+            term = String::new();
+        }
+        term.push(ch);
+    }
+    terms.push(term);
+    let mut monomials: Vec<(Vec<usize>, Ratio<i64>)> = Vec::with_capacity(terms.len());
+    for term in terms {
+        let (coefficient, monomial) = match term.find('M') {
+            Some(index) => term.split_at(index),
+            None => (term.as_str(), ""),
+        };
+        let coefficient = coefficient.strip_suffix('*').unwrap_or(coefficient);
+        let coefficient = match coefficient {
+            "" | "+" if !monomial.is_empty() => Ratio::from_integer(1),
+            "-" if !monomial.is_empty() => Ratio::from_integer(-1),
+            _ => parse_ratio(coefficient).map_err(|_| not_a_number(text))?,
+        };
+        let powers = parse_monomial(monomial).ok_or_else(|| not_a_number(text))?;
+        monomials.push((powers, coefficient));
+    }
+    Ok(M::from_monomials(monomials))
+}
+
+// Reads the powers of M in a monomial such as "M", "M^2", "M2" or "M1M2^3".
+fn parse_monomial(text: &str) -> Option<Vec<usize>> {
+    let mut powers: Vec<usize> = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        rest = rest.strip_prefix('M')?;
+        let level_len = rest.find(|ch: char| !ch.is_ascii_digit()).unwrap_or(rest.len());
+        let level = if level_len == 0 { 1 } else { rest[..level_len].parse::<usize>().ok()? };
+        rest = &rest[level_len..];
+        let power = match rest.strip_prefix('^') {
+            Some(exponent) => {
+                let power_len = exponent.find(|ch: char| !ch.is_ascii_digit()).unwrap_or(exponent.len());
+                rest = &exponent[power_len..];
+                exponent[..power_len].parse::<usize>().ok()?
+            },
+            None => 1,
+        };
+        if level == 0 || level > MAX_M_LEVEL || power > MAX_M_LEVEL {
+            return None;
+        }
+        if powers.len() < level {
+            powers.resize(level, 0);
+        }
+        powers[level - 1] += power;
+    }
+    Some(powers)
+}
+
+fn parse_decimal(text: &str) -> Option<Ratio<i64>> {
+    let (negative, unsigned) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let (mantissa, exponent) = match unsigned.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.strip_prefix('+').unwrap_or(exponent).parse::<i32>().ok()?),
+        None => (unsigned, 0),
+    };
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let fraction = fraction.trim_end_matches('0');
+    if whole.is_empty() && fraction.is_empty() && !mantissa.contains('0') {
+        return None;
+    }
+    if !whole.chars().chain(fraction.chars()).all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    let mut numerator: i64 = 0;
+    for digit in whole.chars().chain(fraction.chars()) {
+        numerator = numerator.checked_mul(10)?.checked_add(digit.to_digit(10)? as i64)?;
+    }
+    let scale = exponent.checked_sub(i32::try_from(fraction.len()).ok()?)?;
+    let power = 10i64.checked_pow(scale.unsigned_abs())?;
+    let value = if scale >= 0 { Ratio::from_integer(numerator.checked_mul(power)?) } else { Ratio::new(numerator, power) };
+    Some(if negative { -value } else { value })
+}
+
+fn not_a_number(text: &str) -> String {
+    format!("'{}' is not a number that can be read exactly.", text)
+}
+
+fn too_large(text: &str) -> String {
+    format!("'{}' is too large to be stored exactly.", text)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_ratio, parse_m};
+    use crate::m::M;
+
+    use num::rational::Ratio;
+
+    #[test]
+    fn reads_fractions_and_decimals() {
+        assert_eq!(parse_ratio("3/4"), Ok(Ratio::new(3, 4)));
+        assert_eq!(parse_ratio("-2"), Ok(Ratio::from_integer(-2)));
+        assert_eq!(parse_ratio("0.125"), Ok(Ratio::new(1, 8)));
+        assert_eq!(parse_ratio("1.5e2"), Ok(Ratio::from_integer(150)));
+    }
+
+    #[test]
+    fn rejects_zero_denominators_and_overflow() {
+        assert!(parse_ratio("1/0").is_err());
+        assert!(parse_ratio("99999999999999999999").is_err());
+        assert!(parse_ratio("1e30").is_err());
+        assert!(parse_ratio("1.5e-2147483648").is_err());
+        assert!(parse_ratio("three").is_err());
+    }
+
+    #[test]
+    fn reads_values_with_m() {
+        assert_eq!(parse_m("2M+3/4"), Ok(M::new(Ratio::from_integer(2), Ratio::new(3, 4))));
+        assert_eq!(parse_m("M1-M2"), Ok(M::from_levels(Ratio::from_integer(0), vec![Ratio::from_integer(1), Ratio::from_integer(-1)])));
+        assert_eq!(parse_m("-M"), Ok(M::new(Ratio::from_integer(-1), Ratio::from_integer(0))));
+        assert!(parse_m("").is_err());
+        assert!(parse_m("M99999999999999").is_err());
+        assert!(parse_m("M^99999999999999").is_err());
+    }
+}
@@ -0,0 +1,30 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct PivotFeedback {
+    pub entering_index: usize,
+    pub leaving_index: usize,
+    pub improving: bool,
+    pub passes_ratio_test: bool,
+    pub pivot_sign_correct: bool,
+    pub follows_rule: bool,
+    pub expected_entering_index: Option<usize>,
+    pub expected_leaving_index: Option<usize>,
+    pub messages: Vec<String>,
+}
+
+impl PivotFeedback {
+    pub fn new(entering_index: usize, leaving_index: usize) -> PivotFeedback {
+        PivotFeedback {
+            entering_index,
+            leaving_index,
+            improving: false,
+            passes_ratio_test: false,
+            pivot_sign_correct: false,
+            follows_rule: false,
+            expected_entering_index: None,
+            expected_leaving_index: None,
+            messages: Vec::new(),
+        }
+    }
+}
\ No newline at end of file
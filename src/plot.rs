@@ -0,0 +1,144 @@
+use crate::geometry::{self, to_f64};
+use crate::report::{column_name, escape_html};
+use crate::tableau::Tableau;
+
+use num::rational::Ratio;
+
+const SIZE: f64 = 480.0;
+const MARGIN: f64 = 50.0;
+
+// Intersects a1 x + a2 y = r with the square [0, bound] x [0, bound], returning the visible segment if there is one.
+fn clip_line(a1: f64, a2: f64, r: f64, bound: f64) -> Option<((f64, f64), (f64, f64))> {
+    let mut points: Vec<(f64, f64)> = Vec::new();
+    if a2 != 0.0 {
+        for &x in [0.0, bound].iter() {
+            let y = (r - a1 * x) / a2;
+            if (0.0..=bound).contains(&y) {
+                points.push((x, y));
+            }
+        }
+    }
+    if a1 != 0.0 {
+        for &y in [0.0, bound].iter() {
+            let x = (r - a2 * y) / a1;
+            if (0.0..=bound).contains(&x) {
+                points.push((x, y));
+            }
+        }
+    }
+    let first = *points.first()?;
+    let second = points.iter()
+                       .copied()
+                       .find(|p| (p.0 - first.0).abs() > 1e-9 || (p.1 - first.1).abs() > 1e-9)?;
+    Some((first, second))
+}
+
+pub fn tableau_to_svg(t: &Tableau) -> Result<String, String> {
+    let region = geometry::region(t);
+    if region.dimension() != 2 {
+        return Err(format!("Feasible region plots need exactly two decision variables, but this problem has {}.", region.dimension()));
+    }
+    let path = geometry::path(t, &region.variables);
+    let extent = region.vertices()
+                       .iter()
+                       .chain(path.iter())
+                       .flatten()
+                       .map(to_f64)
+                       .fold(0.0, f64::max);
+    let bound = (extent * 1.25).ceil().max(1.0);
+    let mut polygon: Vec<(f64, f64)> = region.bounded(Ratio::from_integer(bound as i64))
+                                             .vertices()
+                                             .iter()
+                                             .map(|point| (to_f64(&point[0]), to_f64(&point[1])))
+                                             .collect();
+    let centroid = polygon.iter().fold((0.0, 0.0), |acc, p| (acc.0 + p.0, acc.1 + p.1));
+    let centroid = (centroid.0 / polygon.len().max(1) as f64, centroid.1 / polygon.len().max(1) as f64);
+    polygon.sort_by(|p, q| (p.1 - centroid.1).atan2(p.0 - centroid.0)
+                                             .total_cmp(&(q.1 - centroid.1).atan2(q.0 - centroid.0)));
+    let scale = SIZE / bound;
+    let px = |x: f64| MARGIN + x * scale;
+    let py = |y: f64| MARGIN + SIZE - y * scale;
+    let (x_name, y_name) = (escape_html(&column_name(t, region.variables[0])), escape_html(&column_name(t, region.variables[1])));
+
+    let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{0}\" viewBox=\"0 0 {0} {0}\" font-family=\"sans-serif\" font-size=\"12\">\n",
+                          SIZE + 2.0 * MARGIN);
+    svg.push_str(&format!("<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"black\"/>\n", px(0.0), py(0.0), px(bound), py(0.0)));
+    svg.push_str(&format!("<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"black\"/>\n", px(0.0), py(0.0), px(0.0), py(bound)));
+    svg.push_str(&format!("<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>\n", px(bound), py(0.0) + 30.0, x_name));
+    svg.push_str(&format!("<text x=\"{}\" y=\"{}\">{}</text>\n", px(0.0) - 40.0, py(bound), y_name));
+    svg.push_str(&format!("<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n", px(bound), py(0.0) + 15.0, bound));
+    svg.push_str(&format!("<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>\n", px(0.0) - 5.0, py(bound) + 4.0, bound));
+    if polygon.len() >= 3 {
+        let points: Vec<String> = polygon.iter().map(|p| format!("{:.2},{:.2}", px(p.0), py(p.1))).collect();
+        svg.push_str(&format!("<polygon points=\"{}\" fill=\"#9ecae1\" fill-opacity=\"0.6\" stroke=\"none\"/>\n", points.join(" ")));
+    } else if polygon.len() == 2 {
+        svg.push_str(&format!("<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" stroke=\"#3182bd\" stroke-width=\"4\"/>\n",
+                              px(polygon[0].0), py(polygon[0].1), px(polygon[1].0), py(polygon[1].1)));
+    } else if polygon.is_empty() {
+        svg.push_str(&format!("<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">No feasible region</text>\n", px(bound / 2.0), py(bound / 2.0)));
+    }
+    for constraint in region.constraints.iter().take(t.original_A.len()) {
+        let (a1, a2, r) = (to_f64(&constraint.coefficients[0]), to_f64(&constraint.coefficients[1]), to_f64(&constraint.rhs));
+        if let Some((p, q)) = clip_line(a1, a2, r, bound) {
+            svg.push_str(&format!("<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" stroke=\"#636363\"/>\n", px(p.0), py(p.1), px(q.0), py(q.1)));
+            svg.push_str(&format!("<text x=\"{:.2}\" y=\"{:.2}\" fill=\"#636363\">{}</text>\n", px(q.0) + 4.0, py(q.1) - 4.0, escape_html(&constraint.label)));
+        }
+    }
+    let costs: Vec<Ratio<i64>> = region.variables.iter().map(|&col| t.original_c[col].constant()).collect();
+    let mut levels: Vec<Ratio<i64>> = Vec::new();
+    for point in &path {
+        let level: Ratio<i64> = costs.iter().zip(point.iter()).map(|(c, x)| c * x).sum();
+        if !levels.contains(&level) {
+            levels.push(level);
+        }
+    }
+    for level in &levels {
+        if let Some((p, q)) = clip_line(to_f64(&costs[0]), to_f64(&costs[1]), to_f64(level), bound) {
+            svg.push_str(&format!("<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" stroke=\"#e6550d\" stroke-dasharray=\"6 4\"/>\n", px(p.0), py(p.1), px(q.0), py(q.1)));
+            svg.push_str(&format!("<text x=\"{:.2}\" y=\"{:.2}\" fill=\"#e6550d\">z = {}</text>\n", px(q.0) + 4.0, py(q.1) + 12.0, level));
+        }
+    }
+    if !path.is_empty() {
+        let points: Vec<String> = path.iter().map(|p| format!("{:.2},{:.2}", px(to_f64(&p[0])), py(to_f64(&p[1])))).collect();
+        svg.push_str(&format!("<polyline points=\"{}\" fill=\"none\" stroke=\"#31a354\" stroke-width=\"2\"/>\n", points.join(" ")));
+        for (index, p) in path.iter().enumerate() {
+            let (x, y) = (px(to_f64(&p[0])), py(to_f64(&p[1])));
+            svg.push_str(&format!("<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"5\" fill=\"#31a354\"/>\n", x, y));
+            svg.push_str(&format!("<text x=\"{:.2}\" y=\"{:.2}\" fill=\"#31a354\">{} ({}, {})</text>\n", x + 7.0, y - 7.0, index + 1, p[0], p[1]));
+        }
+    }
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::tableau_to_svg;
+    use crate::tableau::Tableau;
+    use crate::tableau_data::TableauData;
+
+    use num::rational::Ratio;
+
+    fn tableau(a: Vec<Vec<i64>>, b: Vec<i64>, c: Vec<i64>) -> Tableau {
+        let r = Ratio::from_integer;
+        let a = a.into_iter().map(|row| row.into_iter().map(r).collect()).collect();
+        let mut t = Tableau::new(TableauData::from_standard_form(a, b.into_iter().map(r).collect(), c.into_iter().map(r).collect()));
+        t.setup();
+        t.solve();
+        t
+    }
+
+    #[test]
+    fn draws_the_feasible_polygon_through_its_vertices() {
+        // 2x1 + x2 <= 4 and x1 + 3x2 <= 6 cut out (0, 0), (2, 0), (6/5, 8/5) and (0, 2), drawn on a 3 by 3 grid.
+        let t = tableau(vec![vec![2, 1, 1, 0], vec![1, 3, 0, 1]], vec![4, 6], vec![3, 2, 0, 0]);
+        let svg = tableau_to_svg(&t).unwrap();
+        assert!(svg.contains("<polygon points=\"50.00,530.00 370.00,530.00 242.00,274.00 50.00,210.00\""), "{}", svg);
+    }
+
+    #[test]
+    fn rejects_problems_without_two_decision_variables() {
+        let t = tableau(vec![vec![1, 1, 1, 1]], vec![4], vec![1, 1, 1, 0]);
+        assert!(tableau_to_svg(&t).unwrap_err().contains("has 3"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,127 @@
+use crate::geometry::{self, to_f64, Relation};
+use crate::report::column_name;
+use crate::tableau::Tableau;
+
+use num::rational::Ratio;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct FaceData {
+    pub label: String,
+    pub vertex_indecies: Vec<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PolytopeData {
+    pub variables: Vec<usize>,
+    pub variable_names: Vec<String>,
+    pub vertices: Vec<Vec<f64>>,
+    pub faces: Vec<FaceData>,
+    pub path: Vec<Vec<f64>>,
+    pub path_vertex_indecies: Vec<Option<usize>>,
+    pub clipped: bool,
+    pub bound: f64,
+}
+
+fn sub(p: &[f64], q: &[f64]) -> [f64; 3] {
+    [p[0] - q[0], p[1] - q[1], p[2] - q[2]]
+}
+
+fn dot(p: &[f64; 3], q: &[f64; 3]) -> f64 {
+    p[0] * q[0] + p[1] * q[1] + p[2] * q[2]
+}
+
+fn cross(p: &[f64; 3], q: &[f64; 3]) -> [f64; 3] {
+    [p[1] * q[2] - p[2] * q[1], p[2] * q[0] - p[0] * q[2], p[0] * q[1] - p[1] * q[0]]
+}
+
+// Orders the vertices of a face counter-clockwise when viewed from outside along the outward normal.
+fn order_face(vertex_indecies: &mut [usize], vertices: &[Vec<f64>], normal: [f64; 3]) {
+    let count = vertex_indecies.len() as f64;
+    let centroid: Vec<f64> = (0..3).map(|axis| vertex_indecies.iter().map(|&i| vertices[i][axis]).sum::<f64>() / count)
+                                   .collect();
+    let helper = if normal[0].abs() < 0.9 * dot(&normal, &normal).sqrt() { [1.0, 0.0, 0.0] } else { [0.0, 1.0, 0.0] };
+    let u = cross(&normal, &helper);
+    let v = cross(&normal, &u);
+    let angle = |i: &usize| {
+        let d = sub(&vertices[*i], &centroid);
+        dot(&d, &v).atan2(dot(&d, &u))
+    };
+    vertex_indecies.sort_by(|p, q| angle(p).total_cmp(&angle(q)));
+}
+
+pub fn tableau_to_polytope(t: &Tableau) -> Result<PolytopeData, String> {
+    let region = geometry::region(t);
+    if region.dimension() != 3 {
+        return Err(format!("Polytope output needs exactly three decision variables, but this problem has {}.", region.dimension()));
+    }
+    let path = geometry::path(t, &region.variables);
+    let extent = region.vertices()
+                       .iter()
+                       .chain(path.iter())
+                       .flatten()
+                       .map(to_f64)
+                       .fold(0.0, f64::max);
+    let bound = Ratio::from_integer((extent * 1.25).ceil().max(1.0) as i64);
+    let bounded = region.bounded(bound);
+    let exact_vertices = bounded.vertices();
+    let clipped = exact_vertices.iter().flatten().any(|x| x == &bound);
+    let vertices: Vec<Vec<f64>> = exact_vertices.iter().map(|point| point.iter().map(to_f64).collect()).collect();
+    let mut faces: Vec<FaceData> = Vec::new();
+    for constraint in &bounded.constraints {
+        let mut vertex_indecies: Vec<usize> = (0..exact_vertices.len()).filter(|&i| constraint.is_tight(&exact_vertices[i]))
+                                                                       .collect();
+        if vertex_indecies.len() < 3 {
+            continue;
+        }
+        let sign = if constraint.relation == Relation::GreaterEqual { -1.0 } else { 1.0 };
+        let normal = [sign * to_f64(&constraint.coefficients[0]), sign * to_f64(&constraint.coefficients[1]), sign * to_f64(&constraint.coefficients[2])];
+        order_face(&mut vertex_indecies, &vertices, normal);
+        faces.push(FaceData { label: constraint.label.clone(), vertex_indecies });
+    }
+    Ok(PolytopeData {
+        variable_names: region.variables.iter().map(|&col| column_name(t, col)).collect(),
+        variables: region.variables.clone(),
+        path_vertex_indecies: path.iter().map(|point| exact_vertices.iter().position(|vertex| vertex == point)).collect(),
+        path: path.iter().map(|point| point.iter().map(to_f64).collect()).collect(),
+        vertices,
+        faces,
+        clipped,
+        bound: to_f64(&bound),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::tableau_to_polytope;
+    use crate::tableau::Tableau;
+    use crate::tableau_data::TableauData;
+
+    use num::rational::Ratio;
+
+    #[test]
+    fn builds_a_face_for_every_facet_of_a_tetrahedron() {
+        // x1 + x2 + x3 <= 1 with x >= 0 is the tetrahedron on the origin and the three unit points.
+        let r = Ratio::from_integer;
+        let a = vec![vec![r(1), r(1), r(1), r(1)]];
+        let mut t = Tableau::new(TableauData::from_standard_form(a, vec![r(1)], vec![r(1), r(2), r(3), r(0)]));
+        t.setup();
+        t.solve();
+        let polytope = tableau_to_polytope(&t).unwrap();
+        assert_eq!(polytope.variables, [0, 1, 2]);
+        assert_eq!(polytope.vertices.len(), 4);
+        assert!(!polytope.clipped);
+        let labels: Vec<&str> = polytope.faces.iter().map(|face| face.label.as_str()).collect();
+        assert_eq!(labels[1..], ["x1 ≥ 0", "x2 ≥ 0", "x3 ≥ 0"]);
+        let outward = [[1.0, 1.0, 1.0], [-1.0, 0.0, 0.0], [0.0, -1.0, 0.0], [0.0, 0.0, -1.0]];
+        for (face, normal) in polytope.faces.iter().zip(outward.iter()) {
+            assert_eq!(face.vertex_indecies.len(), 3);
+            let [p, q, s] = [0, 1, 2].map(|i| &polytope.vertices[face.vertex_indecies[i]]);
+            let (d, e) = ([q[0] - p[0], q[1] - p[1], q[2] - p[2]], [s[0] - p[0], s[1] - p[1], s[2] - p[2]]);
+            let winding = [d[1] * e[2] - d[2] * e[1], d[2] * e[0] - d[0] * e[2], d[0] * e[1] - d[1] * e[0]];
+            assert!(winding.iter().zip(normal.iter()).map(|(w, n)| w * n).sum::<f64>() > 0.0, "{} is wound inward", face.label);
+        }
+        assert_eq!(polytope.path.last(), Some(&vec![0.0, 0.0, 1.0]));
+        assert!(polytope.path_vertex_indecies.iter().all(Option::is_some));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,320 @@
+use crate::geometry::{Constraint, Relation};
+use crate::tableau_data::TableauData;
+
+use num::rational::Ratio;
+use num::{Signed, Zero};
+
+// A variable as it is declared in a model file. A bound of None means the variable is unbounded in that direction.
+// Integer variables are kept so they can be written back out, but only the LP relaxation is solved.
+#[derive(Debug, Clone)]
+pub struct Variable {
+    pub name: String,
+    pub lower: Option<Ratio<i64>>,
+    pub upper: Option<Ratio<i64>>,
+    pub integer: bool,
+}
+
+// A linear program in the general form model files use, with named variables, inequality constraints and bounds.
+// Each constraint's label is its name.
+#[derive(Debug, Clone)]
+pub struct Problem {
+    pub name: String,
+    pub maximize: bool,
+    pub objective_name: String,
+    pub objective: Vec<Ratio<i64>>,
+    pub variables: Vec<Variable>,
+    pub constraints: Vec<Constraint>,
+}
+
+impl Variable {
+    pub fn new(name: &str) -> Variable {
+        Variable {
+            name: String::from(name),
+            lower: Some(Ratio::zero()),
+            upper: None,
+            integer: false,
+        }
+    }
+}
+
+impl Problem {
+    pub fn new(name: &str) -> Problem {
+        Problem {
+            name: String::from(name),
+            maximize: false,
+            objective_name: String::from("obj"),
+            objective: Vec::new(),
+            variables: Vec::new(),
+            constraints: Vec::new(),
+        }
+    }
+
+    pub fn find_variable(&self, name: &str) -> Option<usize> {
+        self.variables.iter().position(|variable| variable.name == name)
+    }
+
+    pub fn find_constraint(&self, name: &str) -> Option<usize> {
+        self.constraints.iter().position(|constraint| constraint.label == name)
+    }
+
+    // Returns the index of the named variable, adding it with a zero column if it has not been seen yet.
+    pub fn variable_index(&mut self, name: &str) -> usize {
+        if let Some(index) = self.find_variable(name) {
+            return index;
+        }
+        self.variables.push(Variable::new(name));
+        self.objective.push(Ratio::zero());
+        for constraint in self.constraints.iter_mut() {
+            constraint.coefficients.push(Ratio::zero());
+        }
+        self.variables.len() - 1
+    }
+
+    pub fn add_constraint(&mut self, name: &str, relation: Relation, rhs: Ratio<i64>) -> usize {
+        self.constraints.push(Constraint {
+            coefficients: vec![Ratio::zero(); self.variables.len()],
+            relation,
+            rhs,
+            label: String::from(name),
+        });
+        self.constraints.len() - 1
+    }
+
+    // Brings the problem into the form the tableau solves: maximize cx subject to Ax = b and x >= 0.
+    // - A minimization is solved as the maximization of -cx, and marked so its objective is reported negated back.
+    // - A variable with a negative or missing lower bound is split into name+ and name- columns, and marked so its
+    //   value is reported as their difference.
+    // - Any other bound becomes a row of its own.
+    // - Every inequality gets a slack or surplus column, and rows with a negative right hand side are negated.
+    pub fn to_tableau_data(&self) -> TableauData {
+        let mut columns: Vec<(usize, Ratio<i64>)> = Vec::new();
+        let mut variable_names: Vec<String> = Vec::new();
+        let mut free_variables: Vec<usize> = Vec::new();
+        for (index, variable) in self.variables.iter().enumerate() {
+            if variable.lower.is_some_and(|lower| !lower.is_negative()) {
+                columns.push((index, Ratio::from_integer(1)));
+                variable_names.push(variable.name.clone());
+            } else {
+                free_variables.push(columns.len());
+                columns.push((index, Ratio::from_integer(1)));
+                columns.push((index, Ratio::from_integer(-1)));
+                variable_names.push(format!("{}+", variable.name));
+                variable_names.push(format!("{}-", variable.name));
+            }
+        }
+        let mut rows: Vec<Constraint> = self.constraints.clone();
+        for (index, variable) in self.variables.iter().enumerate() {
+            let mut coefficients = vec![Ratio::zero(); self.variables.len()];
+            coefficients[index] = Ratio::from_integer(1);
+            match (variable.lower, variable.upper) {
+                (Some(lower), Some(upper)) if lower == upper => {
+                    rows.push(Constraint { coefficients, relation: Relation::Equal, rhs: lower, label: format!("{}_fixed", variable.name) });
+                },
+                (lower, upper) => {
+                    if let Some(lower) = lower.filter(|lower| !lower.is_zero()) {
+                        rows.push(Constraint { coefficients: coefficients.clone(), relation: Relation::GreaterEqual, rhs: lower, label: format!("{}_lower", variable.name) });
+                    }
+                    if let Some(upper) = upper {
+                        rows.push(Constraint { coefficients, relation: Relation::LessEqual, rhs: upper, label: format!("{}_upper", variable.name) });
+                    }
+                },
+            }
+        }
+        let slack_rows: Vec<usize> = (0..rows.len()).filter(|&row| rows[row].relation != Relation::Equal).collect();
+        for &row in &slack_rows {
+            let kind = if rows[row].relation == Relation::LessEqual { "slack" } else { "surplus" };
+            variable_names.push(format!("{}_{}", rows[row].label, kind));
+        }
+        let mut a: Vec<Vec<Ratio<i64>>> = Vec::with_capacity(rows.len());
+        let mut b: Vec<Ratio<i64>> = Vec::with_capacity(rows.len());
+        for (row_index, row) in rows.iter().enumerate() {
+            let mut values: Vec<Ratio<i64>> = columns.iter().map(|&(index, sign)| row.coefficients[index] * sign).collect();
+            values.extend(slack_rows.iter().map(|&slack_row| match row.relation {
+                Relation::LessEqual if slack_row == row_index => Ratio::from_integer(1),
+                Relation::GreaterEqual if slack_row == row_index => Ratio::from_integer(-1),
+                _ => Ratio::zero(),
+            }));
+            if row.rhs.is_negative() {
+                a.push(values.into_iter().map(|el| -el).collect());
+                b.push(-row.rhs);
+            } else {
+                a.push(values);
+                b.push(row.rhs);
+            }
+        }
+        let sense = Ratio::from_integer(if self.maximize { 1 } else { -1 });
+        let mut c: Vec<Ratio<i64>> = columns.iter().map(|&(index, sign)| self.objective[index] * sign * sense).collect();
+        c.extend(slack_rows.iter().map(|_| Ratio::zero()));
+        let constraint_names = rows.into_iter().map(|row| row.label).collect();
+        let mut data = TableauData::from_standard_form(a, b, c);
+        data.variable_names = variable_names;
+        data.constraint_names = constraint_names;
+        data.decision_variables = Some(columns.len());
+        data.minimize = !self.maximize;
+        data.free_variables = free_variables;
+        data
+    }
+
+    // Reads a tableau problem back as a model: every row is an equality and every variable is non-negative, so the
+    // problem is the one the tableau was given, minimizing the negated costs again if it was marked as a minimization.
+    // Costs involving M have no meaning outside the solver.
+    pub fn from_tableau_data(mut t: TableauData) -> Result<Problem, String> {
+        t.read_strings()?;
+        if t.A_numerators.len() != t.m || t.A_numerators.iter().chain(t.A_denominators.iter()).any(|row| row.len() != t.n) {
+            return Err(format!("The dimensions of A do not match a tableau with {} rows and {} columns.", t.m, t.n));
+        }
+        if t.b_numerators.len() != t.m || t.b_denominators.len() != t.m {
+            return Err(format!("b needs {} values.", t.m));
+        }
+        if t.c_values.is_empty() && (t.c_numerators.len() != t.n || t.c_denominators.len() != t.n) {
+            return Err(format!("c needs {} values.", t.n));
+        }
+        if t.A_denominators.iter().flatten().chain(t.b_denominators.iter()).chain(t.c_denominators.iter()).any(|&den| den == 0) {
+            return Err(String::from("Denominators cannot be zero."));
+        }
+        let objective: Vec<Ratio<i64>> = if t.c_values.is_empty() {
+            if t.c_m_numerators.iter().any(|&num| num != 0) {
+                return Err(String::from("Costs involving M cannot be written to a model file."));
+            }
+            t.c_numerators.iter().zip(t.c_denominators.iter()).map(|(&num, &den)| Ratio::new(num, den)).collect()
+        } else {
+            if t.c_values.len() != t.n {
+                return Err(format!("c needs {} values.", t.n));
+            }
+            if t.c_values.iter().any(|c| c.degree() > 0) {
+                return Err(String::from("Costs involving M cannot be written to a model file."));
+            }
+            t.c_values.iter().map(|c| c.constant()).collect()
+        };
+        let mut problem = Problem::new("tableau");
+        problem.maximize = !t.minimize;
+        for col in 0..t.n {
+            let name = t.variable_names.get(col).cloned().unwrap_or_else(|| format!("x{}", col + 1));
+            problem.variable_index(&name);
+        }
+        if problem.variables.len() != t.n {
+            return Err(String::from("Variable names have to be unique."));
+        }
+        problem.objective = if t.minimize { objective.into_iter().map(|c| -c).collect() } else { objective };
+        for row in 0..t.m {
+            let name = t.constraint_names.get(row).cloned().unwrap_or_else(|| format!("r{}", row + 1));
+            let index = problem.add_constraint(&name, Relation::Equal, Ratio::new(t.b_numerators[row], t.b_denominators[row]));
+            problem.constraints[index].coefficients = t.A_numerators[row].iter()
+                                                                         .zip(t.A_denominators[row].iter())
+                                                                         .map(|(&num, &den)| Ratio::new(num, den))
+                                                                         .collect();
+        }
+        Ok(problem)
+    }
+}
+
+// Writes a value as a decimal, which is what model files expect. Values like 3/4 are written exactly as 0.75, and
+// values like 1/3 that have no finite decimal are written with as many digits as an f64 holds.
+pub fn format_decimal(value: &Ratio<i64>) -> String {
+    let mut rest = *value.denom();
+    let mut places: u32 = 0;
+    let mut scale: i64 = 1;
+    while rest % 10 == 0 || rest % 5 == 0 || rest % 2 == 0 {
+        let factor = if rest % 10 == 0 { 10 } else if rest % 5 == 0 { 5 } else { 2 };
+        rest /= factor;
+        scale = match scale.checked_mul(10 / factor) {
+            Some(scale) => scale,
+            None => break,
+        };
+        places += 1;
+    }
+    let digits = value.numer().checked_mul(scale).filter(|_| rest == 1);
+    match digits {
+        Some(digits) if places == 0 => digits.to_string(),
+        Some(digits) => {
+            let text = format!("{:0>width$}", digits.abs(), width = places as usize + 1);
+            let (whole, fraction) = text.split_at(text.len() - places as usize);
+            format!("{}{}.{}", if digits < 0 { "-" } else { "" }, whole, fraction.trim_end_matches('0'))
+        },
+        None => (*value.numer() as f64 / *value.denom() as f64).to_string(),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{Problem, Variable};
+    use crate::geometry::Relation;
+    use crate::report;
+    use crate::tableau::Tableau;
+    use crate::tableau_data::TableauData;
+
+    use num::rational::Ratio;
+
+    fn r(value: i64) -> Ratio<i64> {
+        Ratio::from_integer(value)
+    }
+
+    fn solve(data: TableauData) -> Tableau {
+        let mut t = Tableau::new(data);
+        t.setup();
+        t.solve();
+        if t.has_artificial_vars && t.big_M_solve_algorithm.as_str() == "two-phase" {
+            t.two_phase_second_phase();
+        }
+        assert!(t.solved, "{}", t.error_message);
+        t.get_solution();
+        t
+    }
+
+    // Minimizes x + y - 2z subject to x + 2y >= 4 and x + y + z <= 6, with z fixed at 1 and y free.
+    fn problem() -> Problem {
+        let mut problem = Problem::new("sample");
+        for name in ["x", "y", "z"] {
+            problem.variable_index(name);
+        }
+        problem.objective = vec![r(1), r(1), r(-2)];
+        let index = problem.add_constraint("c1", Relation::GreaterEqual, r(4));
+        problem.constraints[index].coefficients = vec![r(1), r(2), r(0)];
+        let index = problem.add_constraint("c2", Relation::LessEqual, r(6));
+        problem.constraints[index].coefficients = vec![r(1), r(1), r(1)];
+        problem.variables[1] = Variable { lower: None, ..Variable::new("y") };
+        problem.variables[2] = Variable { lower: Some(r(1)), upper: Some(r(1)), ..Variable::new("z") };
+        problem
+    }
+
+    #[test]
+    fn splits_free_variables_and_fixes_columns_with_a_row() {
+        let data = problem().to_tableau_data();
+        assert_eq!(data.variable_names, vec!["x", "y+", "y-", "z", "c1_surplus", "c2_slack"]);
+        assert_eq!(data.constraint_names, vec!["c1", "c2", "z_fixed"]);
+        assert_eq!(data.free_variables, vec![1]);
+        assert_eq!(data.decision_variables, Some(4));
+        assert!(data.minimize);
+        assert_eq!(data.c_numerators, vec![-1, -1, 1, 2, 0, 0]);
+    }
+
+    #[test]
+    fn reports_a_minimum_with_its_own_sign() {
+        let t = solve(problem().to_tableau_data());
+        assert_eq!(t.objective_value().to_string(), "0");
+        assert_eq!(t.obj.to_string(), "0");
+        let mut shifted = problem();
+        shifted.objective[2] = r(-5);
+        let t = solve(shifted.to_tableau_data());
+        assert_eq!(t.objective_value().to_string(), "-3");
+        assert_eq!(t.obj.to_string(), "3");
+        let markdown = report::tableau_to_markdown(&t);
+        assert!(markdown.contains("Minimize z = x + y+ - y- - 5z"), "{}", markdown);
+        assert!(markdown.contains("z = -3\n"), "{}", markdown);
+        assert!(markdown.contains("y = 2\n") && !markdown.contains("y+ = "), "{}", markdown);
+    }
+
+    #[test]
+    fn reads_a_minimization_back_from_its_tableau() {
+        let mut problem = Problem::new("equalities");
+        problem.variable_index("x");
+        problem.variable_index("y");
+        problem.objective = vec![r(2), r(-3)];
+        let index = problem.add_constraint("c1", Relation::Equal, r(4));
+        problem.constraints[index].coefficients = vec![r(1), r(1)];
+        let read = Problem::from_tableau_data(problem.to_tableau_data()).unwrap();
+        assert!(!read.maximize);
+        assert_eq!(read.objective, problem.objective);
+    }
+}
@@ -0,0 +1,356 @@
+use crate::m::M;
+use crate::scalar::{Scalar, Float};
+use crate::tableau::{Tableau, Step};
+
+use num::rational::Ratio;
+
+struct Section {
+    title: String,
+    lines: Vec<String>,
+    table: Option<Vec<Vec<String>>>,
+    pivot: Option<(usize, usize)>,
+}
+
+pub fn variable_name(index: usize) -> String {
+    format!("x{}", index + 1)
+}
+
+// Uses the names from a model file when the problem came from one.
+pub fn column_name(t: &Tableau, index: usize) -> String {
+    t.variable_names.get(index).cloned().unwrap_or_else(|| variable_name(index))
+}
+
+fn column_names(t: &Tableau, count: usize) -> Vec<String> {
+    (0..count).map(|index| column_name(t, index)).collect()
+}
+
+// A float solve is converted to rationals only so it can be reported, so its values are shown as the decimals they
+// were computed as rather than as the long fractions standing in for them.
+pub fn value_text(t: &Tableau, value: &M) -> String {
+    if t.arithmetic.as_str() == "float" { value.to_decimal() } else { value.to_string() }
+}
+
+pub fn number_text(t: &Tableau, value: &Ratio<i64>) -> String {
+    if t.arithmetic.as_str() == "float" { Float(value.to_f64()).to_string() } else { value.to_string() }
+}
+
+pub fn row_name(t: &Tableau, index: usize) -> String {
+    t.constraint_names.get(index).cloned().unwrap_or_else(|| format!("row {}", index + 1))
+}
+
+// Joins the non-zero terms of a linear expression, dropping unit coefficients and bracketing big-M coefficients.
+pub fn linear_expression(coefficients: &[String], names: &[String]) -> String {
+    let terms: Vec<String> = coefficients.iter()
+                                         .zip(names.iter())
+                                         .filter(|(coefficient, _)| coefficient.as_str() != "0")
+                                         .map(|(coefficient, name)| match coefficient.as_str() {
+                                             "1" => name.clone(),
+                                             "-1" => format!("-{}", name),
+                                             c if c[1..].contains(['+', '-']) => format!("({}){}", c, name),
+                                             c => format!("{}{}", c, name),
+                                         })
+                                         .collect();
+    if terms.is_empty() {
+        return String::from("0");
+    }
+    terms.join(" + ").replace("+ -", "- ")
+}
+
+fn problem_section(t: &Tableau) -> Section {
+    let names = column_names(t, t.original_c.len());
+    let mut lines = if t.minimize {
+        let costs: Vec<String> = t.original_c.iter().map(|c| value_text(t, &-c)).collect();
+        vec![format!("Minimize z = {}", linear_expression(&costs, &names)),
+             String::from("The tableau maximizes -z, so its costs are negated and its objective value is -z.")]
+    } else {
+        vec![format!("Maximize z = {}", linear_expression(&t.original_c.iter().map(|c| value_text(t, c)).collect::<Vec<String>>(), &names))]
+    };
+    lines.push(String::from("subject to"));
+    for (row, b) in t.original_A.iter().zip(t.original_b.iter()) {
+        lines.push(format!("{} = {}", linear_expression(&row.iter().map(|a| number_text(t, a)).collect::<Vec<String>>(), &names), value_text(t, b)));
+    }
+    lines.push(format!("{} ≥ 0", names.join(", ")));
+    Section { title: String::from("Problem"), lines, table: None, pivot: None }
+}
+
+fn standard_form_section(t: &Tableau) -> Section {
+    let mut lines = vec![format!("Solved with the {} simplex method, using the {} rule to choose the entering variable and the {} rule to choose the leaving variable.",
+                                 t.solve_algorithm, t.entering_rule, t.leaving_rule)];
+    if t.big_M_solve_algorithm.as_str() == "numeric" {
+        lines.push(format!("M is replaced by the number {}, so every cost involving M is computed as a plain number.", t.m_value));
+    }
+    if t.arithmetic.as_str() == "float" {
+        lines.push(format!("Solved in floating point arithmetic, so values are rounded. Values within {} of zero are treated as zero in the right hand side, within {} in the reduced cost and within {} in the pivot column.",
+                           t.feasibility_tolerance.to_f64(), t.optimality_tolerance.to_f64(), t.pivot_tolerance.to_f64()));
+    }
+    let original_n = t.original_c.len();
+    if let Some(first) = t.steps.first() {
+        let added_n = first.reduced_cost.len();
+        if added_n > original_n {
+            if t.big_M_solve_algorithm.as_str() == "two-phase" && t.solve_algorithm.as_str() == "standard" {
+                lines.push(String::from("Artificial variables are removed with the two-phase method: phase one maximizes the negative sum of the artificial variables."));
+            }
+            for col in original_n..added_n {
+                let row = first.basis_indecies.iter().position(|&index| index == col);
+                let cost = t.c.get(col).map(|c| value_text(t, c)).unwrap_or_else(|| String::from("0"));
+                match row {
+                    Some(row) => lines.push(format!("{} was added as an artificial variable for row {} with cost {}.", column_name(t, col), row + 1, cost)),
+                    None => lines.push(format!("{} was added as an artificial variable with cost {}.", column_name(t, col), cost)),
+                }
+            }
+        } else {
+            lines.push(String::from("Every row already had a basic variable, so no artificial variables were needed."));
+        }
+        if first.A.len() > t.original_A.len() {
+            lines.push(format!("Row {} was added to bound the sum of the non-basic variables by M.", first.A.len()));
+        }
+    }
+    Section { title: String::from("Standard form"), lines, table: None, pivot: None }
+}
+
+fn step_table(t: &Tableau, step: &Step) -> Vec<Vec<String>> {
+    let n = step.reduced_cost.len();
+    let mut header = vec![String::from("Basis")];
+    header.extend(column_names(t, n));
+    header.push(String::from("b"));
+    let mut table = vec![header];
+    for (row_index, row) in step.A.iter().enumerate() {
+        let mut cells = vec![column_name(t, step.basis_indecies[row_index])];
+        cells.extend(row.iter().map(|el| number_text(t, el)));
+        cells.push(value_text(t, &step.b[row_index]));
+        table.push(cells);
+    }
+    let mut reduced_cost = vec![String::from("z")];
+    reduced_cost.extend(step.reduced_cost.iter().map(|el| value_text(t, el)));
+    reduced_cost.push(value_text(t, &step.obj));
+    table.push(reduced_cost);
+    table
+}
+
+fn step_sections(t: &Tableau) -> Vec<Section> {
+    t.steps.iter()
+           .enumerate()
+           .map(|(index, step)| {
+               let mut lines = match (&step.explanation, step.entering_var_index, step.leaving_var_index) {
+                   (Some(explanation), _, _) => {
+                       let ratios: Vec<String> = explanation.ratios.iter()
+                                                                   .enumerate()
+                                                                   .map(|(index, ratio)| format!("{}: {}",
+                                                                                                 if t.solve_algorithm.as_str() == "dual" { column_name(t, index) } else { format!("row {}", index + 1) },
+                                                                                                 ratio.clone().unwrap_or_else(|| String::from("-"))))
+                                                                   .collect();
+                       vec![explanation.text.clone(), format!("Ratios: {}", ratios.join(", "))]
+                   },
+                   (None, Some(entering_index), Some(leaving_index)) => vec![format!("{} enters and {} leaves from row {}, pivoting on {}.",
+                                                                             column_name(t, entering_index),
+                                                                             column_name(t, step.basis_indecies[leaving_index]),
+                                                                             leaving_index + 1,
+                                                                             number_text(t, &step.A[leaving_index][entering_index]))],
+                   _ => Vec::new(),
+               };
+               if step.explanation.is_none() && step.degenerate_pivot {
+                   lines.push(String::from("This pivot is degenerate, so the objective value does not change."));
+               }
+               if !step.degenerate_rows.is_empty() {
+                   let names: Vec<String> = step.degenerate_rows.iter().map(|&row| column_name(t, step.basis_indecies[row])).collect();
+                   lines.push(format!("Degenerate basis: {} {} basic with value 0.", names.join(", "), if names.len() == 1 { "is" } else { "are" }));
+               }
+               Section {
+                   title: format!("Tableau {}", index + 1),
+                   lines,
+                   table: Some(step_table(t, step)),
+                   pivot: step.leaving_var_index.zip(step.entering_var_index).map(|(row, col)| (row + 1, col + 1)),
+               }
+           })
+           .collect()
+}
+
+// The value of each column of the original problem, with the two columns of a free variable combined back into the
+// variable they were split from.
+fn variable_values(t: &Tableau, solution: &[M]) -> Vec<String> {
+    let n = t.original_c.len();
+    let value = |col: usize| solution.get(col).cloned().unwrap_or_else(M::zero);
+    let mut values = Vec::with_capacity(n);
+    let mut col = 0;
+    while col < n {
+        let name = column_name(t, col);
+        if t.free_variables.contains(&col) && col + 1 < n {
+            values.push(format!("{} = {}", name.strip_suffix('+').unwrap_or(&name), value_text(t, &(&value(col) - value(col + 1)))));
+            col += 2;
+        } else {
+            values.push(format!("{} = {}", name, value_text(t, &value(col))));
+            col += 1;
+        }
+    }
+    values
+}
+
+fn solution_section(t: &Tableau) -> Section {
+    let mut lines = Vec::new();
+    if t.error {
+        lines.push(t.error_message.clone());
+        if !t.infeasible_rows.is_empty() {
+            let rows: Vec<String> = t.infeasible_rows.iter().map(|&row| row_name(t, row)).collect();
+            lines.push(format!("These constraints cannot all be satisfied together: {}. Removing any one of them leaves the rest feasible.", rows.join(", ")));
+        }
+    } else {
+        lines.extend(variable_values(t, &t.solution));
+        lines.push(format!("z = {}", value_text(t, &t.objective_value())));
+        if t.alternative_solutions.len() > 1 {
+            for (index, solution) in t.alternative_solutions.iter().enumerate() {
+                lines.push(format!("Optimal solution {}: {}", index + 1, variable_values(t, solution).join(", ")));
+            }
+        }
+        if !t.optimal_face.is_empty() {
+            lines.push(t.optimal_face.clone());
+        }
+        let degenerate_pivots = t.steps.iter().filter(|step| step.degenerate_pivot).count();
+        if degenerate_pivots > 0 {
+            lines.push(format!("{} of the pivots {} degenerate and left the objective value unchanged.", degenerate_pivots, if degenerate_pivots == 1 { "was" } else { "were" }));
+        }
+        let degenerate_rows = t.find_degenerate_rows();
+        if !degenerate_rows.is_empty() {
+            let names: Vec<String> = degenerate_rows.iter().map(|&row| column_name(t, t.basis_indecies[row])).collect();
+            lines.push(format!("The optimal solution is degenerate: {} {} basic with value 0, so more than one basis describes this point.",
+                               names.join(", "), if names.len() == 1 { "is" } else { "are" }));
+        }
+    }
+    Section { title: String::from("Solution"), lines, table: None, pivot: None }
+}
+
+fn sections(t: &Tableau) -> Vec<Section> {
+    let mut sections = vec![problem_section(t), standard_form_section(t)];
+    sections.extend(step_sections(t));
+    sections.push(solution_section(t));
+    sections
+}
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn tableau_to_html(t: &Tableau) -> String {
+    let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Simplex solution</title>\n<style>\n");
+    html.push_str("body { font-family: sans-serif; max-width: 60em; margin: auto; }\n");
+    html.push_str("table { border-collapse: collapse; margin: 1em 0; }\n");
+    html.push_str("th, td { border: 1px solid #999; padding: 0.25em 0.75em; text-align: right; }\n");
+    html.push_str("td.pivot { background: #ffe08a; font-weight: bold; }\n");
+    html.push_str("tr:last-child td { border-top: 2px solid #333; }\n");
+    html.push_str("</style>\n</head>\n<body>\n<h1>Simplex solution</h1>\n");
+    for section in sections(t) {
+        html.push_str(&format!("<h2>{}</h2>\n", escape_html(&section.title)));
+        for line in &section.lines {
+            html.push_str(&format!("<p>{}</p>\n", escape_html(line)));
+        }
+        if let Some(table) = &section.table {
+            html.push_str("<table>\n");
+            for (row_index, row) in table.iter().enumerate() {
+                html.push_str("<tr>");
+                for (col_index, cell) in row.iter().enumerate() {
+                    if row_index == 0 || col_index == 0 {
+                        html.push_str(&format!("<th>{}</th>", escape_html(cell)));
+                    } else if section.pivot == Some((row_index, col_index)) {
+                        html.push_str(&format!("<td class=\"pivot\">{}</td>", escape_html(cell)));
+                    } else {
+                        html.push_str(&format!("<td>{}</td>", escape_html(cell)));
+                    }
+                }
+                html.push_str("</tr>\n");
+            }
+            html.push_str("</table>\n");
+        }
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+pub fn tableau_to_markdown(t: &Tableau) -> String {
+    let mut markdown = String::from("# Simplex solution\n\n");
+    for section in sections(t) {
+        markdown.push_str(&format!("## {}\n\n", section.title));
+        for line in &section.lines {
+            markdown.push_str(&format!("{}\n\n", line));
+        }
+        if let Some(table) = &section.table {
+            for (row_index, row) in table.iter().enumerate() {
+                let cells: Vec<String> = row.iter()
+                                            .enumerate()
+                                            .map(|(col_index, cell)| if section.pivot == Some((row_index, col_index)) { format!("**[{}]**", cell) } else { cell.clone() })
+                                            .collect();
+                markdown.push_str(&format!("| {} |\n", cells.join(" | ")));
+                if row_index == 0 {
+                    markdown.push_str(&format!("|{}\n", "---|".repeat(row.len())));
+                }
+            }
+            markdown.push('\n');
+        }
+    }
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tableau_to_html, tableau_to_markdown};
+    use crate::latex::tableau_to_latex;
+    use crate::scalar::{Scalar, Float};
+    use crate::tableau::Tableau;
+    use crate::tableau_data::TableauData;
+
+    use num::rational::Ratio;
+
+    // Maximizes 3/10 x1 + 1/5 x2 subject to 7/10 x1 + 3/10 x2 <= 41/10 and x1 + 3 x2 <= 6, with the optimum at
+    // x1 = 35/6 and x2 = 1/18.
+    fn solve(arithmetic: &str) -> Tableau {
+        let r = Ratio::new;
+        let a = vec![vec![r(7, 10), r(3, 10), r(1, 1), r(0, 1)], vec![r(1, 1), r(3, 1), r(0, 1), r(1, 1)]];
+        let mut data = TableauData::from_standard_form(a, vec![r(41, 10), r(6, 1)], vec![r(3, 10), r(1, 5), r(0, 1), r(0, 1)]);
+        data.arithmetic = String::from(arithmetic);
+        let mut t = if arithmetic == "float" { run::<Float>(data).to_rational().unwrap() } else { run(data) };
+        assert!(t.solved, "{}", t.error_message);
+        t.get_solution();
+        t
+    }
+
+    fn run<T: Scalar>(data: TableauData) -> Tableau<T> {
+        let mut t = Tableau::new(data);
+        t.setup();
+        t.solve();
+        t
+    }
+
+    #[test]
+    fn reports_every_section_in_order() {
+        // Maximizes 3x1 + 2x2 subject to 2x1 + x2 <= 4 and x1 + 3x2 <= 6, which takes two pivots.
+        let r = Ratio::from_integer;
+        let a = vec![vec![r(2), r(1), r(1), r(0)], vec![r(1), r(3), r(0), r(1)]];
+        let mut t = Tableau::new(TableauData::from_standard_form(a, vec![r(4), r(6)], vec![r(3), r(2), r(0), r(0)]));
+        t.setup();
+        t.solve();
+        t.get_solution();
+        let markdown = tableau_to_markdown(&t);
+        let titles: Vec<&str> = markdown.lines().filter_map(|line| line.strip_prefix("## ")).collect();
+        assert_eq!(titles, ["Problem", "Standard form", "Tableau 1", "Tableau 2", "Tableau 3", "Solution"]);
+        assert!(markdown.contains("x1 = 6/5\n\nx2 = 8/5\n\nx3 = 0\n\nx4 = 0\n\nz = 34/5"), "{}", markdown);
+        let html = tableau_to_html(&t);
+        assert_eq!(html.matches("<h2>").count(), titles.len());
+        assert_eq!(html.matches("<td class=\"pivot\">").count(), 2, "{}", html);
+    }
+
+    #[test]
+    fn shows_float_values_as_decimals() {
+        let t = solve("float");
+        let markdown = tableau_to_markdown(&t);
+        assert!(markdown.contains("Maximize z = 0.3x1 + 0.2x2"), "{}", markdown);
+        assert!(markdown.contains("| **[0.7]** |"), "{}", markdown);
+        assert!(markdown.contains("x1 = 5.83333333333333"), "{}", markdown);
+        assert!(!markdown.contains("00000000000000/"), "{}", markdown);
+        let latex = tableau_to_latex(&t);
+        assert!(latex.contains("\\boxed{0.7}") && latex.contains("5.83333333333333"), "{}", latex);
+        assert!(!latex.contains("\\frac"), "{}", latex);
+        let markdown = tableau_to_markdown(&solve("exact"));
+        assert!(markdown.contains("Maximize z = 3/10x1 + 1/5x2") && markdown.contains("x1 = 35/6"), "{}", markdown);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,189 @@
+use crate::parse::parse_ratio;
+
+use num::rational::Ratio;
+use num::{Signed, Zero, One};
+
+// The numbers a tableau is built from. Exact rationals are the default and make every step reproducible by hand,
+// while Float trades exactness for speed on larger problems and relies on the tableau's tolerances instead.
+pub trait Scalar:
+    Copy + Ord + std::fmt::Debug + std::fmt::Display + Zero + One + std::iter::Sum<Self>
+    + std::ops::Add<Output = Self> + std::ops::Sub<Output = Self> + std::ops::Mul<Output = Self>
+    + std::ops::Div<Output = Self> + std::ops::Neg<Output = Self>
+    + std::ops::AddAssign + std::ops::SubAssign + std::ops::MulAssign + std::ops::DivAssign
+{
+    // The name reported back in the arithmetic field of the response.
+    const ARITHMETIC: &'static str;
+
+    fn from_ratio(r: Ratio<i64>) -> Self;
+
+    fn from_f64(x: f64) -> Self;
+
+    // The value as a rational, or an error if it has none that fits in 64 bits. Float is read from its shortest decimal
+    // form, so 0.25 is 1/4 and not the nearest fraction with a huge denominator.
+    fn to_ratio(&self) -> Result<Ratio<i64>, String>;
+
+    fn to_f64(&self) -> f64;
+
+    fn abs(&self) -> Self;
+}
+
+impl Scalar for Ratio<i64> {
+    const ARITHMETIC: &'static str = "exact";
+
+    fn from_ratio(r: Ratio<i64>) -> Self {
+        r
+    }
+
+    fn from_f64(x: f64) -> Self {
+        Ratio::approximate_float(x).unwrap_or_else(Ratio::zero)
+    }
+
+    fn to_ratio(&self) -> Result<Ratio<i64>, String> {
+        Ok(*self)
+    }
+
+    fn to_f64(&self) -> f64 {
+        *self.numer() as f64 / *self.denom() as f64
+    }
+
+    fn abs(&self) -> Self {
+        Signed::abs(self)
+    }
+}
+
+// An f64 with a total order so it can be used wherever the tableau sorts or takes the minimum of its values.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Float(pub f64);
+
+impl Scalar for Float {
+    const ARITHMETIC: &'static str = "float";
+
+    fn from_ratio(r: Ratio<i64>) -> Self {
+        Float(*r.numer() as f64 / *r.denom() as f64)
+    }
+
+    fn from_f64(x: f64) -> Self {
+        Float(x)
+    }
+
+    fn to_ratio(&self) -> Result<Ratio<i64>, String> {
+        if !self.0.is_finite() {
+            return Err(format!("The result has a value of {}, which is not a number that can be reported.", self.0));
+        }
+        parse_ratio(&self.0.to_string()).ok()
+                                        .or_else(|| Ratio::approximate_float(self.0))
+                                        .ok_or_else(|| format!("The result has a value of {}, which is too large to report as a fraction.", self.0))
+    }
+
+    fn to_f64(&self) -> f64 {
+        self.0
+    }
+
+    fn abs(&self) -> Self {
+        Float(self.0.abs())
+    }
+}
+
+impl PartialEq for Float {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for Float {}
+
+impl PartialOrd for Float {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Float {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.partial_cmp(&other.0).unwrap_or_else(|| self.0.total_cmp(&other.0))
+    }
+}
+
+impl std::fmt::Display for Float {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Zero for Float {
+    fn zero() -> Self {
+        Float(0.0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0.0
+    }
+}
+
+impl One for Float {
+    fn one() -> Self {
+        Float(1.0)
+    }
+}
+
+impl std::iter::Sum<Self> for Float {
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = Self>,
+        {
+            Float(iter.map(|el| el.0).sum())
+        }
+}
+
+impl std::ops::Neg for Float {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Float(-self.0)
+    }
+}
+
+macro_rules! float_op {
+    ($op:ident, $fn:ident, $assign_op:ident, $assign_fn:ident, $symbol:tt) => {
+        impl std::ops::$op for Float {
+            type Output = Self;
+
+            fn $fn(self, rhs: Self) -> Self {
+                Float(self.0 $symbol rhs.0)
+            }
+        }
+
+        impl std::ops::$assign_op for Float {
+            fn $assign_fn(&mut self, rhs: Self) {
+                self.0 = self.0 $symbol rhs.0;
+            }
+        }
+    };
+}
+
+float_op!(Add, add, AddAssign, add_assign, +);
+float_op!(Sub, sub, SubAssign, sub_assign, -);
+float_op!(Mul, mul, MulAssign, mul_assign, *);
+float_op!(Div, div, DivAssign, div_assign, /);
+
+
+#[cfg(test)]
+mod tests {
+    use super::{Scalar, Float};
+
+    use num::rational::Ratio;
+
+    #[test]
+    fn reads_floats_from_their_decimal_form() {
+        assert_eq!(Float(0.25).to_ratio(), Ok(Ratio::new(1, 4)));
+        assert_eq!(Float(-1.5).to_ratio(), Ok(Ratio::new(-3, 2)));
+        assert_eq!(Float(0.1 + 0.2).to_ratio(), Ok(Ratio::new(7500000000000001, 25000000000000000)));
+    }
+
+    #[test]
+    fn rejects_floats_without_a_rational_value() {
+        assert!(Float(f64::NAN).to_ratio().is_err());
+        assert!(Float(f64::INFINITY).to_ratio().is_err());
+        assert!(Float(1e300).to_ratio().is_err());
+    }
+}
@@ -0,0 +1,71 @@
+use crate::tableau::Tableau;
+use crate::tableau_data::TableauData;
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const SESSION_LIFETIME: Duration = Duration::from_secs(60 * 60);
+
+struct Session {
+    tableau: Tableau,
+    last_used: Instant,
+}
+
+pub struct SessionStore {
+    sessions: Mutex<HashMap<u64, Session>>,
+    next_id: Mutex<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionData {
+    pub id: Option<u64>,
+    pub tableau: TableauData,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PivotData {
+    pub entering_index: usize,
+    pub leaving_index: usize,
+}
+
+impl Default for SessionStore {
+    fn default() -> SessionStore {
+        SessionStore::new()
+    }
+}
+
+impl SessionStore {
+    pub fn new() -> SessionStore {
+        SessionStore {
+            sessions: Mutex::new(HashMap::new()),
+            next_id: Mutex::new(1),
+        }
+    }
+
+    pub fn create(&self, tableau: Tableau) -> u64 {
+        let mut next_id = self.next_id.lock().unwrap();
+        let id = *next_id;
+        *next_id += 1;
+        let mut sessions = self.sessions.lock().unwrap();
+        SessionStore::remove_expired(&mut sessions);
+        sessions.insert(id, Session { tableau, last_used: Instant::now() });
+        id
+    }
+
+    // Runs f against the stored tableau, returning None if the session does not exist or has expired.
+    pub fn with_session<F, R>(&self, id: u64, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut Tableau) -> R,
+    {
+        let mut sessions = self.sessions.lock().unwrap();
+        SessionStore::remove_expired(&mut sessions);
+        let session = sessions.get_mut(&id)?;
+        session.last_used = Instant::now();
+        Some(f(&mut session.tableau))
+    }
+
+    fn remove_expired(sessions: &mut HashMap<u64, Session>) {
+        sessions.retain(|_, session| session.last_used.elapsed() < SESSION_LIFETIME);
+    }
+}
\ No newline at end of file
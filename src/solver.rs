@@ -0,0 +1,52 @@
+use crate::scalar::{Scalar, Float};
+use crate::tableau::Tableau;
+use crate::tableau_data::TableauData;
+
+// Solves a problem the way /solve does, in the arithmetic it asks for. Alongside the tableau it returns whether the
+// method stopped because it cycled, in which case the tableau is marked as an error but still holds every step taken.
+// An error about the request itself, such as an unknown arithmetic, a problem whose arrays do not fit together or a rule
+// the chosen algorithm does not support, is returned as Err since nothing was solved, as is a float result that has no
+// value that can be reported.
+pub fn solve(data: TableauData, debug: bool) -> Result<(Tableau, bool), String> {
+    let arithmetic = data.arithmetic.clone();
+    let mut t: Tableau = match arithmetic.as_str() {
+        "" | "exact" => run_simplex(Tableau::new(data), debug)?,
+        "float" => run_simplex(Tableau::<Float>::new(data), debug)?.to_rational()?,
+        other => return Err(format!("Unknown arithmetic '{}'. Supported arithmetic is: exact, float.", other)),
+    };
+    let cycled = !t.solved && !t.error;
+    if t.error {
+        return Ok((t, cycled));
+    }
+    if t.solved {
+        t.get_solution();
+        if t.arithmetic.as_str() == "exact" {
+            t.find_alternative_solutions();
+        }
+    } else {
+        t.error = true;
+        t.error_message = String::from("Stuck in a cycle, terminted solution process.");
+    }
+    Ok((t, cycled))
+}
+
+fn run_simplex<T: Scalar>(mut t: Tableau<T>, debug: bool) -> Result<Tableau<T>, String> {
+    t.DEBUG = debug;
+    if !t.error {
+        t.validate_rules();
+    }
+    if t.error {
+        return Err(t.error_message);
+    }
+    t.setup();
+    t.solve();
+    if t.has_artificial_vars && t.big_M_solve_algorithm.as_str() == "two-phase" {
+        t.two_phase_second_phase();
+    }
+    // The deletion filter runs in the tableau's own arithmetic, since rational stand-ins for float values can have
+    // denominators large enough to overflow once they are pivoted on.
+    if t.error && t.infeasibility_certificate.is_some() {
+        t.infeasible_rows = t.find_infeasible_subset();
+    }
+    Ok(t)
+}
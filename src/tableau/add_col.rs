@@ -1,20 +1,20 @@
 use crate::tableau::Tableau;
 use crate::m::M;
+use crate::scalar::Scalar;
 
-use num::rational::Ratio;
-
-impl Tableau {
-    pub fn add_col(&mut self, row_index: usize, c: M) {
-        if &c.M != &Ratio::new(0i64,1) {
+impl<T: Scalar> Tableau<T> {
+    pub fn add_col(&mut self, row_index: usize, c: M<T>) {
+        if !c.m().is_zero() {
             self.has_artificial_vars = true;
         }
+        let c = if self.big_M_solve_algorithm.as_str() == "numeric" { M::new(T::zero(), c.evaluate(&self.m_value)) } else { c };
         for row in &mut self.A {
-            row.push(Ratio::new(0i64,1));
+            row.push(T::zero());
         }
-        self.A[row_index][self.n] = Ratio::new(1i64,1);
+        self.A[row_index][self.n] = T::one();
         self.c.push(c);
         if self.big_M_solve_algorithm.as_str() == "two-phase" {
-            self.two_phase_c.push(M::new(Ratio::new(0i64,1), Ratio::new(-1i64,1)));
+            self.two_phase_c.push(M::new(T::zero(), -T::one()));
         }
         self.basis_indecies[row_index] = self.n;
         self.n += 1;
@@ -0,0 +1,100 @@
+use crate::tableau::Tableau;
+use crate::m::M;
+
+use num::rational::Ratio;
+
+impl Tableau {
+    pub fn apply_pivot(&mut self, entering_index: usize, leaving_index: usize) {
+        if self.solved {
+            self.error = true;
+            self.error_message = String::from("The tableau is already optimal, so no more pivots can be made.");
+            return;
+        }
+        if entering_index >= self.n {
+            self.error = true;
+            self.error_message = format!("Entering column {} is outside of the tableau, which only has {} columns.", entering_index, self.n);
+            return;
+        }
+        if leaving_index >= self.m {
+            self.error = true;
+            self.error_message = format!("Leaving row {} is outside of the tableau, which only has {} rows.", leaving_index, self.m);
+            return;
+        }
+        if self.A[leaving_index][entering_index] == Ratio::new(0i64,1) {
+            self.error = true;
+            self.error_message = format!("Cannot pivot on row {} and column {} because the pivot element is zero.", leaving_index, entering_index);
+            return;
+        }
+        self.entering_var_index = Some(entering_index);
+        self.leaving_var_index = Some(leaving_index);
+        self.pivot();
+        self.solved = self.is_optimal();
+        // The optimum of phase one only tells whether the problem is feasible, so the session carries on in phase two.
+        if self.solved && self.has_artificial_vars && self.big_M_solve_algorithm.as_str() == "two-phase" {
+            self.end_phase_one();
+            if self.error {
+                return;
+            }
+            self.solved = self.is_optimal();
+        }
+        if self.solved && self.solve_algorithm.as_str() == "standard" && self.has_artificial_vars {
+            let artificial = (0..self.m).find(|&row| self.basis_indecies[row] >= self.original_c.len() && self.b[row] != M::zero());
+            if let Some(row) = artificial {
+                self.error = true;
+                self.error_message = format!("The artificial variable x{} is still basic with value {} at the optimum, so the underlying LP is infeasible.",
+                                             self.basis_indecies[row] + 1, self.b[row]);
+                return;
+            }
+        }
+        if self.solved {
+            self.get_solution();
+        }
+    }
+
+    fn is_optimal(&self) -> bool {
+        let zero = M::new(Ratio::new(0i64,1), Ratio::new(0i64,1));
+        match self.solve_algorithm.as_str() {
+            "dual" => self.b.iter().all(|el| el >= &zero),
+            _ => self.reduced_cost.iter().all(|el| el >= &zero),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tableau::Tableau;
+    use crate::tableau_data::TableauData;
+
+    use num::rational::Ratio;
+
+    fn session(a: Vec<Vec<i64>>, b: Vec<i64>, c: Vec<i64>, big_M_solve_algorithm: &str) -> Tableau {
+        let ratios = |values: Vec<i64>| values.into_iter().map(Ratio::from_integer).collect::<Vec<Ratio<i64>>>();
+        let mut data = TableauData::from_standard_form(a.into_iter().map(ratios).collect(), ratios(b), ratios(c));
+        data.big_M_solve_algorithm = String::from(big_M_solve_algorithm);
+        let mut t = Tableau::new(data);
+        t.DEBUG = false;
+        t.setup();
+        t
+    }
+
+    #[test]
+    fn two_phase_session_moves_on_to_phase_two() {
+        let mut t = session(vec![vec![2, 3]], vec![6], vec![1, 0], "two-phase");
+        assert!(t.has_artificial_vars);
+        t.apply_pivot(0, 0);
+        assert!(!t.error, "{}", t.error_message);
+        assert!(!t.has_artificial_vars);
+        assert!(t.solved);
+        assert_eq!(t.solution[0].to_string(), "3");
+        t.apply_pivot(1, 0);
+        assert!(t.error);
+    }
+
+    #[test]
+    fn big_m_session_reports_a_basic_artificial_variable() {
+        let mut t = session(vec![vec![1, 1], vec![1, 0]], vec![1, 3], vec![1, 0], "big-M");
+        t.apply_pivot(0, 0);
+        assert!(t.error);
+        assert!(t.error_message.contains("infeasible"), "{}", t.error_message);
+    }
+}
@@ -1,32 +1,30 @@
 use crate::tableau::Tableau;
-
 use crate::m::M;
-use num::rational::Ratio;
+use crate::scalar::Scalar;
 
-impl Tableau {
+impl<T: Scalar> Tableau<T> {
     pub fn calc_reduced_cost(&mut self) {
-        let basis_cost:Vec<&M>;
-        if self.has_artificial_vars && self.big_M_solve_algorithm.as_str() == "two-phase" {
-            basis_cost = self.basis_indecies.iter()
-                                            .map(|&index| &self.two_phase_c[index])
-                                            .collect();
+        let basis_cost: Vec<&M<T>> = if self.has_artificial_vars && self.big_M_solve_algorithm.as_str() == "two-phase" {
+            self.basis_indecies.iter()
+                               .map(|&index| &self.two_phase_c[index])
+                               .collect()
         } else {
-            basis_cost = self.basis_indecies.iter()
-                                            .map(|&index| &self.c[index])
-                                            .collect();
-        }
+            self.basis_indecies.iter()
+                               .map(|&index| &self.c[index])
+                               .collect()
+        };
         if self.DEBUG {
             print!("basis_cost: [");
             for el in &basis_cost {
                 print!("{}, ", el);
             }
-            print!("]\n");
+            println!("]");
         }
         self.reduced_cost.drain(..);
         for col in 0..self.n {
-            self.reduced_cost.push(M::new(Ratio::new(0i64, 1), Ratio::new(0i64, 1)));
-            for row in 0..self.m {
-                self.reduced_cost[col] += &(&self.A[row][col] * basis_cost[row]);
+            self.reduced_cost.push(M::zero());
+            for (cost, row) in basis_cost.iter().zip(self.A.iter()) {
+                self.reduced_cost[col] += &(*cost * &row[col]);
             }
             if self.has_artificial_vars && self.big_M_solve_algorithm.as_str() == "two-phase" {
                 self.reduced_cost[col] -= &self.two_phase_c[col];
@@ -38,5 +36,6 @@ impl Tableau {
                              .zip(self.b.iter())
                              .map(|(&c, b)| b*c )
                              .sum();
+        self.snap_to_tolerances();
     }
 }
\ No newline at end of file
@@ -0,0 +1,133 @@
+use crate::tableau::Tableau;
+use crate::m::M;
+use crate::pivot_feedback::PivotFeedback;
+
+use num::rational::Ratio;
+
+impl Tableau {
+    pub fn check_pivot(&self, entering_index: usize, leaving_index: usize) -> PivotFeedback {
+        let mut feedback = PivotFeedback::new(entering_index, leaving_index);
+        if entering_index >= self.n || leaving_index >= self.m {
+            feedback.messages.push(format!("The tableau only has {} rows and {} columns, so row {} and column {} cannot be pivoted on.", self.m, self.n, leaving_index + 1, entering_index + 1));
+            return feedback;
+        }
+        let zero = M::new(Ratio::new(0i64,1), Ratio::new(0i64,1));
+        let pivot_element = self.A[leaving_index][entering_index];
+        let mut rule = self.clone();
+        match self.solve_algorithm.as_str() {
+            "standard" => {
+                feedback.improving = self.reduced_cost[entering_index] < zero;
+                if !feedback.improving {
+                    feedback.messages.push(format!("x{} does not improve the objective because its reduced cost {} is not negative.", entering_index + 1, self.reduced_cost[entering_index]));
+                }
+                feedback.pivot_sign_correct = pivot_element > Ratio::new(0i64,1);
+                if !feedback.pivot_sign_correct {
+                    feedback.messages.push(format!("The pivot element {} in row {} is not positive, so this row is not part of the minimum ratio test.", pivot_element, leaving_index + 1));
+                } else {
+                    let min_ratio = self.A.iter()
+                                          .map(|row| &row[entering_index])
+                                          .zip(self.b.iter())
+                                          .filter(|&(a, _)| a > &Ratio::new(0i64,1))
+                                          .map(|(a, b)| b / a)
+                                          .min()
+                                          .unwrap();
+                    let ratio = &self.b[leaving_index] / &pivot_element;
+                    feedback.passes_ratio_test = ratio == min_ratio;
+                    if !feedback.passes_ratio_test {
+                        feedback.messages.push(format!("Row {} fails the minimum ratio test: its ratio {} is larger than the smallest ratio {} in the column of x{}.", leaving_index + 1, ratio, min_ratio, entering_index + 1));
+                    }
+                }
+                rule.select_entering_var();
+                if !rule.solved && !rule.error {
+                    rule.select_leaving_var();
+                }
+            },
+            "dual" => {
+                feedback.improving = self.b[leaving_index] < zero;
+                if !feedback.improving {
+                    feedback.messages.push(format!("Row {} cannot leave because its right hand side {} is not negative.", leaving_index + 1, self.b[leaving_index]));
+                }
+                feedback.pivot_sign_correct = pivot_element < Ratio::new(0i64,1);
+                if !feedback.pivot_sign_correct {
+                    feedback.messages.push(format!("The pivot element {} in the column of x{} is not negative, so this column is not part of the ratio test.", pivot_element, entering_index + 1));
+                } else {
+                    let max_ratio = self.A[leaving_index].iter()
+                                                         .zip(self.reduced_cost.iter())
+                                                         .filter(|&(a, _)| a < &Ratio::new(0i64,1))
+                                                         .map(|(a, rc)| rc / a)
+                                                         .max()
+                                                         .unwrap();
+                    let ratio = &self.reduced_cost[entering_index] / &pivot_element;
+                    feedback.passes_ratio_test = ratio == max_ratio;
+                    if !feedback.passes_ratio_test {
+                        feedback.messages.push(format!("x{} fails the ratio test: its ratio {} is further from zero than the ratio {} in row {}.", entering_index + 1, ratio, max_ratio, leaving_index + 1));
+                    }
+                }
+                rule.select_leaving_var();
+                if !rule.solved && !rule.error {
+                    rule.select_entering_var();
+                }
+            },
+            _ => {
+                feedback.messages.push(String::from("Unknown selection for solve algorithm."));
+                return feedback;
+            }
+        }
+        if rule.solved {
+            feedback.messages.push(String::from("The tableau is already optimal, so no pivot should be made."));
+            return feedback;
+        }
+        if rule.error {
+            feedback.messages.push(rule.error_message);
+            return feedback;
+        }
+        feedback.expected_entering_index = rule.entering_var_index;
+        feedback.expected_leaving_index = rule.leaving_var_index;
+        feedback.follows_rule = feedback.expected_entering_index == Some(entering_index) && feedback.expected_leaving_index == Some(leaving_index);
+        if !feedback.follows_rule {
+            rule.explain_step();
+            if let Some(explanation) = rule.explanation {
+                feedback.messages.push(format!("Using the {} entering rule and the {} leaving rule, {}", self.entering_rule, self.leaving_rule, explanation.text));
+            }
+        }
+        feedback
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tableau::Tableau;
+    use crate::tableau_data::TableauData;
+
+    use num::rational::Ratio;
+
+    // Maximizes 3x1 + 2x2 subject to x1 + x2 <= 4 and x1 + 3x2 <= 6, where Dantzig's rule pivots on row 1 of x1.
+    fn tableau() -> Tableau {
+        let r = Ratio::from_integer;
+        let a = vec![vec![r(1), r(1), r(1), r(0)], vec![r(1), r(3), r(0), r(1)]];
+        let mut t = Tableau::new(TableauData::from_standard_form(a, vec![r(4), r(6)], vec![r(3), r(2), r(0), r(0)]));
+        t.DEBUG = false;
+        t.setup();
+        t
+    }
+
+    #[test]
+    fn accepts_the_pivot_the_rules_choose() {
+        let feedback = tableau().check_pivot(0, 0);
+        assert!(feedback.improving && feedback.passes_ratio_test && feedback.pivot_sign_correct && feedback.follows_rule);
+        assert!(feedback.messages.is_empty(), "{:?}", feedback.messages);
+    }
+
+    #[test]
+    fn rejects_invalid_pivots() {
+        let t = tableau();
+        let feedback = t.check_pivot(0, 1);
+        assert!(feedback.improving && !feedback.passes_ratio_test && !feedback.follows_rule);
+        assert_eq!((feedback.expected_entering_index, feedback.expected_leaving_index), (Some(0), Some(0)));
+        let feedback = t.check_pivot(2, 0);
+        assert!(!feedback.improving && !feedback.follows_rule);
+        let feedback = t.check_pivot(4, 0);
+        assert!(!feedback.pivot_sign_correct && !feedback.follows_rule);
+        assert_eq!(feedback.messages.len(), 1);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,213 @@
+use crate::tableau::Tableau;
+use crate::enumeration::{Enumeration, Vertex};
+use crate::geometry::{combinations, independent_rows, solve_square};
+use crate::m::M;
+
+use num::rational::Ratio;
+use num::Zero;
+
+const MAX_BASES: u64 = 200_000;
+
+impl Tableau {
+    // Brute force check of the simplex result: every choice of m columns of the original problem that forms a basis
+    // is solved exactly, and the basic solution is kept if it is feasible. Rows that depend on the others are dropped
+    // first, so m is the rank of A. At each feasible basis the edges leaving it are followed too, and an edge that
+    // never leaves the region while the objective grows is returned as the direction the problem is unbounded along.
+    // Objectives are reported the way objective_value reports them, negated back for a minimization. Only meant for
+    // small problems.
+    pub fn enumerate_vertices(&self) -> Result<Enumeration, String> {
+        let n = self.original_c.len();
+        let b: Vec<Ratio<i64>> = self.original_b.iter().map(|el| el.constant()).collect();
+        let kept = match independent_rows(&self.original_A, &b) {
+            Some(kept) => kept,
+            None => return Ok(Enumeration { vertices: Vec::new(), unbounded_direction: None, minimize: self.minimize }),
+        };
+        let a: Vec<&Vec<Ratio<i64>>> = kept.iter().map(|&row| &self.original_A[row]).collect();
+        let b: Vec<Ratio<i64>> = kept.iter().map(|&row| b[row]).collect();
+        let m = kept.len();
+        let mut bases: u64 = 1;
+        for i in 0..m.min(n) {
+            bases = bases * (n - i) as u64 / (i + 1) as u64;
+            if bases > MAX_BASES {
+                return Err(format!("Choosing {} of {} columns gives more than {} possible bases, which is too many to enumerate.", m, n, MAX_BASES));
+            }
+        }
+        let mut vertices: Vec<Vertex> = Vec::new();
+        let mut unbounded_direction = None;
+        for basis in combinations(n, m) {
+            let rows: Vec<Vec<Ratio<i64>>> = (0..m).map(|row| basis.iter().map(|&col| a[row][col]).collect())
+                                                   .collect();
+            let values = match solve_square(rows.clone(), b.clone()) {
+                Some(values) => values,
+                None => continue,
+            };
+            if values.iter().any(|value| value < &Ratio::zero()) {
+                continue;
+            }
+            if unbounded_direction.is_none() {
+                unbounded_direction = self.unbounded_edge(&a, &basis, &rows);
+            }
+            let mut solution = vec![Ratio::zero(); n];
+            for (&col, value) in basis.iter().zip(values) {
+                solution[col] = value;
+            }
+            if vertices.iter().any(|vertex| vertex.solution == solution) {
+                continue;
+            }
+            let objective: M = solution.iter()
+                                       .zip(self.original_c.iter())
+                                       .map(|(x, c)| x * c)
+                                       .sum();
+            let objective = if self.minimize { -&objective } else { objective };
+            vertices.push(Vertex { basis_indecies: basis, solution, objective });
+        }
+        Ok(Enumeration { vertices, unbounded_direction, minimize: self.minimize })
+    }
+
+    // Follows the edge from a basis along each nonbasic column. An edge is unbounded if none of the basic variables
+    // decrease along it, so it never leaves the region, and the objective increases along it.
+    fn unbounded_edge(&self, a: &[&Vec<Ratio<i64>>], basis: &[usize], rows: &[Vec<Ratio<i64>>]) -> Option<Vec<Ratio<i64>>> {
+        let n = self.original_c.len();
+        for col in (0..n).filter(|col| !basis.contains(col)) {
+            let column: Vec<Ratio<i64>> = a.iter().map(|row| row[col]).collect();
+            let change = solve_square(rows.to_vec(), column)?;
+            if change.iter().any(|el| el > &Ratio::zero()) {
+                continue;
+            }
+            let gain = &self.original_c[col] - basis.iter()
+                                                    .zip(change.iter())
+                                                    .map(|(&basic, el)| &self.original_c[basic] * el)
+                                                    .sum::<M>();
+            if gain > M::zero() {
+                let mut direction = vec![Ratio::zero(); n];
+                direction[col] = Ratio::from_integer(1);
+                for (&basic, el) in basis.iter().zip(change.iter()) {
+                    direction[basic] = -el;
+                }
+                return Some(direction);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::enumeration::EnumerationData;
+    use crate::tableau::Tableau;
+    use crate::tableau_data::TableauData;
+
+    use num::rational::Ratio;
+
+    fn r(value: i64) -> Ratio<i64> {
+        Ratio::from_integer(value)
+    }
+
+    fn problem(a: Vec<Vec<i64>>, b: Vec<i64>, c: Vec<i64>, big_M_solve_algorithm: &str) -> TableauData {
+        let ratios = |values: Vec<i64>| values.into_iter().map(r).collect::<Vec<Ratio<i64>>>();
+        let mut data = TableauData::from_standard_form(a.into_iter().map(ratios).collect(), ratios(b), ratios(c));
+        data.big_M_solve_algorithm = String::from(big_M_solve_algorithm);
+        data
+    }
+
+    fn solve(data: TableauData) -> Tableau {
+        let mut t = Tableau::new(data);
+        t.setup();
+        t.solve();
+        if t.has_artificial_vars && t.big_M_solve_algorithm.as_str() == "two-phase" {
+            t.two_phase_second_phase();
+        }
+        if t.solved {
+            t.get_solution();
+        }
+        t
+    }
+
+    fn assert_matches_enumeration(a: Vec<Vec<i64>>, b: Vec<i64>, c: Vec<i64>) {
+        for big_M_solve_algorithm in ["two-phase", "big-M"] {
+            let t = solve(problem(a.clone(), b.clone(), c.clone(), big_M_solve_algorithm));
+            assert!(t.solved, "{}: {}", big_M_solve_algorithm, t.error_message);
+            let vertices = t.enumerate_vertices().unwrap().vertices;
+            let best = vertices.iter().map(|vertex| &vertex.objective).max().unwrap();
+            assert_eq!(&t.obj, best, "{}", big_M_solve_algorithm);
+            let solution: Vec<_> = t.solution.iter().take(c.len()).map(|el| el.constant()).collect();
+            assert!(vertices.iter().any(|vertex| &vertex.objective == best && vertex.solution == solution), "{}", big_M_solve_algorithm);
+        }
+    }
+
+    #[test]
+    fn matches_simplex_with_slack_basis() {
+        assert_matches_enumeration(vec![vec![1, 1, 1, 0], vec![1, 3, 0, 1]], vec![4, 6], vec![3, 2, 0, 0]);
+    }
+
+    #[test]
+    fn matches_simplex_with_artificial_variables() {
+        assert_matches_enumeration(vec![vec![1, 1, -1, 0], vec![1, 1, 0, 1]], vec![2, 4], vec![1, 2, 0, 0]);
+        assert_matches_enumeration(vec![vec![1, 2, 1], vec![3, 1, 0]], vec![4, 3], vec![-1, -1, 0]);
+    }
+
+    #[test]
+    fn detects_alternative_optima() {
+        let t = Tableau::new(problem(vec![vec![1, 1, 1, 0], vec![1, 0, 0, 1]], vec![4, 3], vec![1, 1, 0, 0], "two-phase"));
+        let vertices = t.enumerate_vertices().unwrap().vertices;
+        let best = vertices.iter().map(|vertex| &vertex.objective).max().unwrap();
+        assert_eq!(vertices.iter().filter(|vertex| &vertex.objective == best).count(), 2);
+    }
+
+    #[test]
+    fn finds_no_vertices_when_infeasible() {
+        let t = Tableau::new(problem(vec![vec![1, 1, 1, 0], vec![1, 1, 0, -1]], vec![2, 4], vec![1, 1, 0, 0], "two-phase"));
+        assert!(t.enumerate_vertices().unwrap().vertices.is_empty());
+    }
+
+    #[test]
+    fn detects_unbounded_edges() {
+        let t = Tableau::new(problem(vec![vec![1, -1, 1]], vec![1], vec![1, 0, 0], "two-phase"));
+        let enumeration = t.enumerate_vertices().unwrap();
+        assert!(!enumeration.vertices.is_empty());
+        assert_eq!(enumeration.unbounded_direction, Some(vec![r(1), r(1), r(0)]));
+        let data = EnumerationData::new(enumeration);
+        assert!(data.error);
+        assert_eq!(data.optimal_vertex_count, 0);
+    }
+
+    #[test]
+    fn finds_no_unbounded_edge_when_bounded_in_the_objective() {
+        let t = Tableau::new(problem(vec![vec![1, -1, 1]], vec![1], vec![-1, -1, 0], "two-phase"));
+        let data = EnumerationData::new(t.enumerate_vertices().unwrap());
+        assert!(!data.error, "{}", data.error_message);
+        assert_eq!(data.optimal_objective, Some(String::from("0")));
+    }
+
+    #[test]
+    fn drops_dependent_rows() {
+        let t = Tableau::new(problem(vec![vec![1, 1, 1, 0], vec![2, 2, 2, 0], vec![1, 3, 0, 1]], vec![4, 8, 6], vec![3, 2, 0, 0], "two-phase"));
+        let best = t.enumerate_vertices().unwrap().vertices.into_iter().map(|vertex| vertex.objective).max();
+        assert_eq!(best.map(|objective| objective.to_string()), Some(String::from("12")));
+        let t = Tableau::new(problem(vec![vec![1, 0], vec![0, 1], vec![1, 1]], vec![1, 2, 3], vec![1, 1], "two-phase"));
+        let vertices = t.enumerate_vertices().unwrap().vertices;
+        assert_eq!(vertices.len(), 1);
+        assert_eq!(vertices[0].solution, vec![r(1), r(2)]);
+    }
+
+    #[test]
+    fn ranks_vertices_of_a_minimization_by_the_lowest_objective() {
+        // Minimizes 2x1 + x2 subject to x1 + x2 >= 2 and x1 <= 3, which the tableau maximizes as -2x1 - x2.
+        let mut data = problem(vec![vec![1, 1, -1, 0], vec![1, 0, 0, 1]], vec![2, 3], vec![-2, -1, 0, 0], "two-phase");
+        data.minimize = true;
+        let t = solve(data);
+        assert!(t.solved, "{}", t.error_message);
+        let data = EnumerationData::new(t.enumerate_vertices().unwrap());
+        assert_eq!(data.optimal_objective, Some(t.objective_value().to_string()));
+        assert_eq!(data.optimal_objective, Some(String::from("2")));
+        let optimal: Vec<&Vec<String>> = data.vertices.iter().filter(|vertex| vertex.optimal).map(|vertex| &vertex.solution).collect();
+        assert_eq!(optimal, vec![&vec![String::from("0"), String::from("2"), String::from("0"), String::from("3")]]);
+        assert!(data.vertices.iter().any(|vertex| vertex.objective == "6"));
+    }
+
+    #[test]
+    fn finds_no_vertices_when_dependent_rows_disagree() {
+        let t = Tableau::new(problem(vec![vec![1, 1, 1, 0], vec![2, 2, 2, 0], vec![1, 3, 0, 1]], vec![4, 7, 6], vec![3, 2, 0, 0], "two-phase"));
+        assert!(t.enumerate_vertices().unwrap().vertices.is_empty());
+    }
+}
\ No newline at end of file
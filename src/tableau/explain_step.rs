@@ -0,0 +1,115 @@
+use crate::tableau::{Tableau, Explanation};
+use crate::m::M;
+use crate::scalar::Scalar;
+
+impl<T: Scalar> Tableau<T> {
+    // Describes why select_entering_var and select_leaving_var picked the current pivot, for display next to the step.
+    pub fn explain_step(&mut self) {
+        let (entering_index, leaving_index) = match (self.entering_var_index, self.leaving_var_index) {
+            (Some(entering_index), Some(leaving_index)) => (entering_index, leaving_index),
+            _ => {
+                self.explanation = None;
+                return;
+            }
+        };
+        let zero = T::zero();
+        let zero_m = M::zero();
+        match self.solve_algorithm.as_str() {
+            "standard" => {
+                let ratios: Vec<Option<M<T>>> = self.A.iter()
+                                                   .zip(self.b.iter())
+                                                   .map(|(row, b)| if row[entering_index] > zero { Some(b / &row[entering_index]) } else { None })
+                                                   .collect();
+                let entering_reason = match self.entering_rule.as_str() {
+                    "bland" => format!("{} enters because it is the lowest indexed variable with a negative reduced cost ({})", self.column_name(entering_index), self.reduced_cost[entering_index]),
+                    _ => format!("{} enters because its reduced cost {} is the most negative", self.column_name(entering_index), self.reduced_cost[entering_index]),
+                };
+                let mut leaving_reason = format!("row {} leaves because {} is the smallest ratio among positive entries",
+                                                 leaving_index + 1, Self::ratio_text(&self.b[leaving_index], &self.A[leaving_index][entering_index]));
+                let ties: Vec<usize> = (0..self.m).filter(|&row| ratios[row].is_some() && ratios[row] == ratios[leaving_index])
+                                                  .collect();
+                if ties.len() > 1 {
+                    let tie_reason = match self.leaving_rule.as_str() {
+                        "bland" => format!("its basic variable {} has the lowest index", self.column_name(self.basis_indecies[leaving_index])),
+                        "largest-pivot" => format!("its pivot element {} is the largest", self.A[leaving_index][entering_index]),
+                        "lexicographic" => String::from("it is lexicographically smallest after dividing by the pivot element"),
+                        _ => String::from("it is the first of the tied rows"),
+                    };
+                    leaving_reason.push_str(&format!("; rows {} tie, and the {} rule picks row {} because {}",
+                                                     Self::join_indecies(&ties), self.leaving_rule, leaving_index + 1, tie_reason));
+                }
+                self.explanation = Some(Explanation {
+                    entering_index,
+                    leaving_index,
+                    entering_rule: self.entering_rule.clone(),
+                    leaving_rule: self.leaving_rule.clone(),
+                    ratios: ratios.iter().map(|ratio| ratio.as_ref().map(|r| r.to_string())).collect(),
+                    text: if self.b[leaving_index] == zero_m {
+                        format!("{}; {}. The minimum ratio is 0, so this pivot is degenerate: the basis changes but the objective value stays at {}.",
+                                entering_reason, leaving_reason, self.obj)
+                    } else {
+                        format!("{}; {}.", entering_reason, leaving_reason)
+                    },
+                    degenerate: self.b[leaving_index] == zero_m,
+                });
+            },
+            "dual" => {
+                let ratios: Vec<Option<M<T>>> = self.A[leaving_index].iter()
+                                                                  .zip(self.reduced_cost.iter())
+                                                                  .map(|(a, rc)| if a < &zero { Some(rc / a) } else { None })
+                                                                  .collect();
+                let leaving_reason = match self.leaving_rule.as_str() {
+                    "bland" => format!("row {} leaves because it is the first row with a negative right hand side ({})", leaving_index + 1, self.b[leaving_index]),
+                    _ => format!("row {} leaves because its right hand side {} is the most negative", leaving_index + 1, self.b[leaving_index]),
+                };
+                let mut entering_reason = format!("{} enters because {} is the ratio closest to zero among negative entries",
+                                                  self.column_name(entering_index), Self::ratio_text(&self.reduced_cost[entering_index], &self.A[leaving_index][entering_index]));
+                let ties: Vec<usize> = (0..self.n).filter(|&col| ratios[col].is_some() && ratios[col] == ratios[entering_index])
+                                                  .collect();
+                if ties.len() > 1 {
+                    let tie_reason = match self.entering_rule.as_str() {
+                        "largest-pivot" => format!("its pivot element {} is the largest in size", self.A[leaving_index][entering_index]),
+                        _ => String::from("it has the lowest index"),
+                    };
+                    entering_reason.push_str(&format!("; columns {} tie, and the {} rule picks {} because {}",
+                                                      Self::join_indecies(&ties), self.entering_rule, self.column_name(entering_index), tie_reason));
+                }
+                self.explanation = Some(Explanation {
+                    entering_index,
+                    leaving_index,
+                    entering_rule: self.entering_rule.clone(),
+                    leaving_rule: self.leaving_rule.clone(),
+                    ratios: ratios.iter().map(|ratio| ratio.as_ref().map(|r| r.to_string())).collect(),
+                    text: if self.reduced_cost[entering_index] == zero_m {
+                        format!("{}; {}. The ratio is 0, so this pivot is degenerate: the basis changes but the objective value stays at {}.",
+                                leaving_reason, entering_reason, self.obj)
+                    } else {
+                        format!("{}; {}.", leaving_reason, entering_reason)
+                    },
+                    degenerate: self.reduced_cost[entering_index] == zero_m,
+                });
+            },
+            _ => {
+                self.explanation = None;
+            }
+        }
+    }
+
+    // The name the problem gave the column, falling back to x1, x2, ... for columns it did not name.
+    fn column_name(&self, col: usize) -> String {
+        self.variable_names.get(col).cloned().unwrap_or_else(|| format!("x{}", col + 1))
+    }
+
+    fn ratio_text(numerator: &M<T>, denominator: &T) -> String {
+        let bracket = |text: String| if text.contains(['/', '+', '-']) { format!("({})", text) } else { text };
+        format!("{}/{}", bracket(numerator.to_string()), bracket(denominator.to_string()))
+    }
+
+    fn join_indecies(indecies: &[usize]) -> String {
+        let names: Vec<String> = indecies.iter().map(|index| (index + 1).to_string()).collect();
+        match names.split_last() {
+            Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+            _ => names.join(""),
+        }
+    }
+}
\ No newline at end of file
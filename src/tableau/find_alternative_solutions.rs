@@ -0,0 +1,113 @@
+use crate::tableau::Tableau;
+use crate::m::M;
+
+use num::rational::Ratio;
+use std::collections::VecDeque;
+
+const MAX_OPTIMAL_BASES: usize = 1000;
+
+impl Tableau {
+    // Starting from an optimal tableau, pivots on every non-basic column with a zero reduced cost to visit each
+    // optimal basis, collecting the distinct optimal basic solutions and describing the optimal face they span.
+    pub fn find_alternative_solutions(&mut self) {
+        let zero = M::new(Ratio::new(0i64,1), Ratio::new(0i64,1));
+        let mut start = self.clone();
+        start.steps.clear();
+        start.get_solution();
+        let mut visited: Vec<Vec<usize>> = Vec::new();
+        let mut queue: VecDeque<Tableau> = VecDeque::new();
+        let mut solutions: Vec<Vec<M>> = Vec::new();
+        let mut rays: Vec<usize> = Vec::new();
+        let mut sorted_basis = start.basis_indecies.clone();
+        sorted_basis.sort();
+        visited.push(sorted_basis);
+        queue.push_back(start);
+        while let Some(t) = queue.pop_front() {
+            if !solutions.contains(&t.solution) {
+                solutions.push(t.solution.clone());
+            }
+            for col in 0..t.n {
+                if t.basis_indecies.contains(&col) || t.reduced_cost[col] != zero {
+                    continue;
+                }
+                let ratios: Vec<Option<M>> = t.A.iter()
+                                                .zip(t.b.iter())
+                                                .map(|(row, b)| if row[col] > Ratio::new(0i64,1) { Some(b / &row[col]) } else { None })
+                                                .collect();
+                let min_ratio = match ratios.iter().flatten().min() {
+                    Some(ratio) => ratio.clone(),
+                    None => {
+                        if !rays.contains(&col) {
+                            rays.push(col);
+                        }
+                        continue;
+                    }
+                };
+                for row in (0..t.m).filter(|&row| ratios[row].as_ref() == Some(&min_ratio)) {
+                    if visited.len() >= MAX_OPTIMAL_BASES {
+                        break;
+                    }
+                    let mut next = t.clone();
+                    next.entering_var_index = Some(col);
+                    next.leaving_var_index = Some(row);
+                    next.pivot();
+                    next.steps.clear();
+                    let mut sorted_basis = next.basis_indecies.clone();
+                    sorted_basis.sort();
+                    if visited.contains(&sorted_basis) {
+                        continue;
+                    }
+                    visited.push(sorted_basis);
+                    next.get_solution();
+                    queue.push_back(next);
+                }
+            }
+        }
+        let mut optimal_face = match solutions.len() {
+            1 => String::from("The optimal basic solution is unique."),
+            2 => String::from("There are 2 optimal basic solutions, and every point on the edge between them is also optimal."),
+            k => format!("There are {} optimal basic solutions, and every convex combination of them is also optimal.", k),
+        };
+        if !rays.is_empty() {
+            let names: Vec<String> = rays.iter().map(|&col| self.variable_names.get(col).cloned().unwrap_or_else(|| format!("x{}", col + 1))).collect();
+            optimal_face.push_str(&format!(" The optimal face is unbounded: {} can be increased without limit without changing the objective value {}.",
+                                           names.join(", "), self.objective_value()));
+        }
+        if visited.len() >= MAX_OPTIMAL_BASES {
+            optimal_face.push_str(&format!(" Only the first {} optimal bases were searched.", MAX_OPTIMAL_BASES));
+        }
+        self.alternative_solutions = solutions;
+        self.optimal_face = optimal_face;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::m::M;
+    use crate::tableau::Tableau;
+    use crate::tableau_data::TableauData;
+
+    use num::rational::Ratio;
+
+    #[test]
+    fn every_alternative_solution_satisfies_the_constraints() {
+        let ratios = |values: Vec<i64>| values.into_iter().map(Ratio::from_integer).collect::<Vec<Ratio<i64>>>();
+        let a = vec![ratios(vec![1, 1, 1, 0]), ratios(vec![1, 0, 0, 1])];
+        let mut t = Tableau::new(TableauData::from_standard_form(a, ratios(vec![4, 3]), ratios(vec![1, 1, 0, 0])));
+        t.DEBUG = false;
+        t.setup();
+        t.solve();
+        t.get_solution();
+        t.find_alternative_solutions();
+        assert!(t.solved, "{}", t.error_message);
+        assert_eq!(t.alternative_solutions.len(), 2);
+        for solution in &t.alternative_solutions {
+            let x = &solution[..t.original_c.len()];
+            assert!(x.iter().all(|el| el >= &M::zero()));
+            for (row, b) in t.original_A.iter().zip(t.original_b.iter()) {
+                let lhs: M = x.iter().zip(row.iter()).map(|(el, a)| el * a).sum();
+                assert_eq!(&lhs, b);
+            }
+        }
+    }
+}
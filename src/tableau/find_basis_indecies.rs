@@ -1,18 +1,17 @@
 use crate::tableau::Tableau;
 use crate::m::M;
+use crate::scalar::Scalar;
 
-use num::rational::Ratio;
-
-impl Tableau {
+impl<T: Scalar> Tableau<T> {
     pub fn find_basis_indecies(&mut self) {
         let mut a_cols = vec![Vec::with_capacity(self.m);self.n];
-        for row in 0..self.m {
-            for col in 0..self.n {
-                a_cols[col].push(self.A[row][col]);
+        for row in self.A.iter() {
+            for (a_col, el) in a_cols.iter_mut().zip(row.iter()) {
+                a_col.push(*el);
             }
         }
-        let mut I = vec![Ratio::new(0i64,1);self.m];
-        I[0] = Ratio::new(1i64,1);
+        let mut I = vec![T::zero();self.m];
+        I[0] = T::one();
         match self.solve_algorithm.as_str() {
             "standard" => {
                 for i in 0..self.m {
@@ -22,11 +21,12 @@ impl Tableau {
                             self.basis_indecies[i] = index;
                         },
                         None => {
-                            self.add_col(i, M::new(Ratio::new(-1i64,1), Ratio::new(0i64,1)));
+                            self.add_col(i, M::new(-T::one(), T::zero()));
                         }
                     }
                     I.rotate_right(1);
                 }
+                self.initial_basis_indecies = self.basis_indecies.clone();
             },
             "dual" => {
                 let mut neg_I = I.clone();
@@ -35,9 +35,9 @@ impl Tableau {
                     let res = a_cols.iter().position(|col| col == &I || col == &neg_I);
                     match res {
                         Some(index) => {
-                            if self.A[i][index] == Ratio::new(-1i64,1) {
+                            if self.A[i][index] == -T::one() {
                                 for el in self.A[i].iter_mut() {
-                                    *el *= -1;
+                                    *el = -*el;
                                 }
                                 self.b[i] *= -1;
                             }
@@ -54,7 +54,6 @@ impl Tableau {
             _ => {
                 self.error = true;
                 self.error_message = String::from("Unknown selection for solve algorithm.");
-                return;
             }
         }
     }
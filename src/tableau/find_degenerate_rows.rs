@@ -0,0 +1,50 @@
+use crate::tableau::Tableau;
+use crate::m::M;
+use crate::scalar::Scalar;
+
+impl<T: Scalar> Tableau<T> {
+    // Rows whose basic variable currently has the value 0. A pivot on one of these rows has a minimum ratio of 0, so
+    // it changes the basis without moving to a new point or changing the objective value.
+    pub fn find_degenerate_rows(&self) -> Vec<usize> {
+        let zero = M::zero();
+        (0..self.m).filter(|&row| self.b[row] == zero)
+                   .collect()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::tableau::Tableau;
+    use crate::tableau_data::TableauData;
+
+    use num::rational::Ratio;
+
+    fn solve(a: Vec<Vec<i64>>, b: Vec<i64>, c: Vec<i64>) -> Tableau {
+        let ratios = |values: Vec<i64>| values.into_iter().map(Ratio::from_integer).collect::<Vec<Ratio<i64>>>();
+        let mut t = Tableau::new(TableauData::from_standard_form(a.into_iter().map(ratios).collect(), ratios(b), ratios(c)));
+        t.DEBUG = false;
+        t.setup();
+        t.solve();
+        assert!(t.solved, "{}", t.error_message);
+        t
+    }
+
+    #[test]
+    fn flags_a_pivot_on_a_row_with_value_zero() {
+        // Maximizes 2x1 + x2 subject to x1 - x2 <= 0, x1 <= 2 and x2 <= 3. x1 first enters on the first row at 0.
+        let t = solve(vec![vec![1, -1, 1, 0, 0], vec![1, 0, 0, 1, 0], vec![0, 1, 0, 0, 1]], vec![0, 2, 3], vec![2, 1, 0, 0, 0]);
+        let flags: Vec<bool> = t.steps.iter().map(|step| step.degenerate_pivot).collect();
+        assert_eq!(flags, [true, false, false, false]);
+        assert_eq!(t.steps[0].degenerate_rows, [0]);
+        assert!(t.find_degenerate_rows().is_empty());
+    }
+
+    #[test]
+    fn finds_a_degenerate_optimum() {
+        // Maximizes 3x1 + 2x2 subject to x1 + x2 <= 2 and x1 + 3x2 <= 2, where both rows tie in the ratio test for x1.
+        let t = solve(vec![vec![1, 1, 1, 0], vec![1, 3, 0, 1]], vec![2, 2], vec![3, 2, 0, 0]);
+        assert!(t.steps.iter().all(|step| !step.degenerate_pivot));
+        assert_eq!(t.find_degenerate_rows(), [1]);
+    }
+}
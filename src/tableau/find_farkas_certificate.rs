@@ -0,0 +1,47 @@
+use crate::tableau::Tableau;
+use crate::certificate::InfeasibilityCertificate;
+use crate::scalar::Scalar;
+
+impl<T: Scalar> Tableau<T> {
+    // Reads the row multipliers off the final phase one tableau. The column that started in the basis for row i was
+    // the i-th unit column, so its reduced cost plus its phase one cost is the i-th multiplier. Phase one stopped
+    // because every reduced cost is non-negative, which gives yA >= 0 over the original columns, and yb is the
+    // negative phase one objective.
+    pub fn find_farkas_certificate(&self) -> InfeasibilityCertificate<T> {
+        let multipliers = self.initial_basis_indecies.iter()
+                                                     .map(|&index| self.reduced_cost[index].constant() + self.two_phase_c[index].constant())
+                                                     .collect();
+        InfeasibilityCertificate::new(multipliers, &self.original_A, &self.original_b, self.feasibility_tolerance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tableau::Tableau;
+    use crate::tableau_data::TableauData;
+
+    use num::rational::Ratio;
+
+    #[test]
+    fn verifies_the_multipliers_of_an_infeasible_problem() {
+        // x1 + x2 <= 1 and x1 + x2 >= 3 cannot both hold, so phase one ends with a non-zero objective.
+        let r = Ratio::from_integer;
+        let a = vec![vec![r(1), r(1), r(1), r(0)], vec![r(1), r(1), r(0), r(-1)]];
+        let mut t: Tableau = Tableau::new(TableauData::from_standard_form(a, vec![r(1), r(3)], vec![r(1), r(1), r(0), r(0)]));
+        t.DEBUG = false;
+        t.setup();
+        t.solve();
+        t.two_phase_second_phase();
+        assert!(t.error && !t.solved);
+        let certificate = t.infeasibility_certificate.clone().unwrap();
+        assert!(certificate.verified);
+        let y = &certificate.multipliers;
+        for col in 0..t.original_c.len() {
+            assert!(t.original_A.iter().zip(y.iter()).map(|(row, y)| row[col] * y).sum::<Ratio<i64>>() >= r(0));
+        }
+        assert!(y[0] + y[1] * r(3) < r(0), "{:?}", y);
+        let mut tampered = t.clone();
+        tampered.original_b[1] = tampered.original_b[0].clone();
+        assert!(!tampered.find_farkas_certificate().verified);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::tableau::Tableau;
+use crate::m::M;
+use crate::scalar::Scalar;
+
+impl<T: Scalar> Tableau<T> {
+    // Deletion filter over the rows of the original problem: each row is dropped in turn, and stays dropped if the
+    // rows that are left are still infeasible. The rows that remain form a minimal infeasible subset, so removing any
+    // one of them makes the rest feasible. The non-negativity of the variables is always kept.
+    pub fn find_infeasible_subset(&self) -> Vec<usize> {
+        let mut rows: Vec<usize> = (0..self.original_A.len()).collect();
+        if self.is_feasible_subset(&rows) {
+            return Vec::new();
+        }
+        let mut i = 0;
+        while i < rows.len() {
+            let mut candidate = rows.clone();
+            candidate.remove(i);
+            if self.is_feasible_subset(&candidate) {
+                i += 1;
+            } else {
+                rows = candidate;
+            }
+        }
+        rows
+    }
+
+    // Runs phase one of the two-phase method with Bland's rule on the given rows of the original problem. Rows with a
+    // negative right hand side are negated first so the standard setup accepts them. In float arithmetic the phase one
+    // objective counts as zero once it is within the feasibility tolerance.
+    fn is_feasible_subset(&self, rows: &[usize]) -> bool {
+        let mut t = self.clone();
+        t.DEBUG = false;
+        t.A = rows.iter().map(|&row| self.original_A[row].clone()).collect();
+        t.b = rows.iter().map(|&row| self.original_b[row].clone()).collect();
+        for (row, b) in t.A.iter_mut().zip(t.b.iter_mut()) {
+            if b.constant() < T::zero() {
+                for el in row.iter_mut() {
+                    *el = -*el;
+                }
+                *b *= -1;
+            }
+        }
+        t.n = self.original_c.len();
+        t.m = rows.len();
+        t.c = vec![M::zero(); t.n];
+        t.two_phase_c = vec![M::zero(); t.n];
+        t.basis_indecies = vec![t.n; t.m];
+        t.has_artificial_vars = false;
+        t.solve_algorithm = String::from("standard");
+        t.big_M_solve_algorithm = String::from("two-phase");
+        t.entering_rule = String::from("bland");
+        t.leaving_rule = String::from("bland");
+        t.solved = false;
+        t.error = false;
+        t.steps.clear();
+        t.setup();
+        if !t.has_artificial_vars {
+            return !t.error;
+        }
+        while !t.solved && !t.error {
+            t.solve();
+        }
+        t.solved && t.obj.snap(&t.feasibility_tolerance).is_zero()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::scalar::{Scalar, Float};
+    use crate::tableau::Tableau;
+    use crate::tableau_data::TableauData;
+
+    use num::rational::Ratio;
+
+    fn infeasible_rows(a: Vec<Vec<Ratio<i64>>>, b: Vec<Ratio<i64>>, c: Vec<Ratio<i64>>, arithmetic: &str) -> Vec<usize> {
+        let mut data = TableauData::from_standard_form(a, b, c);
+        data.arithmetic = String::from(arithmetic);
+        match arithmetic {
+            "float" => run(Tableau::<Float>::new(data)),
+            _ => run::<Ratio<i64>>(Tableau::new(data)),
+        }
+    }
+
+    fn run<T: Scalar>(mut t: Tableau<T>) -> Vec<usize> {
+        t.DEBUG = false;
+        t.setup();
+        t.solve();
+        if t.has_artificial_vars && t.big_M_solve_algorithm.as_str() == "two-phase" {
+            t.two_phase_second_phase();
+        }
+        assert!(t.error && !t.solved && t.infeasibility_certificate.is_some(), "{}", t.error_message);
+        t.find_infeasible_subset()
+    }
+
+    fn solve(a: Vec<Vec<i64>>, b: Vec<i64>, c: Vec<i64>) -> Vec<usize> {
+        let ratios = |values: Vec<i64>| values.into_iter().map(Ratio::from_integer).collect::<Vec<Ratio<i64>>>();
+        infeasible_rows(a.into_iter().map(ratios).collect(), ratios(b), ratios(c), "exact")
+    }
+
+    #[test]
+    fn finds_the_conflicting_rows_after_phase_one() {
+        assert_eq!(solve(vec![vec![-1, -1], vec![1, 1]], vec![1, 1], vec![1, 1]), vec![0]);
+    }
+
+    #[test]
+    fn drops_rows_that_are_not_part_of_the_conflict() {
+        let a = vec![vec![1, 1, 0, 0], vec![1, 0, 1, 0], vec![1, 1, 0, -1]];
+        assert_eq!(solve(a, vec![2, 5, 3], vec![1, 1, 0, 0]), vec![0, 2]);
+    }
+
+    #[test]
+    fn finds_the_conflicting_rows_in_float_arithmetic() {
+        let (one, third, seventh) = (Ratio::from_integer(1), Ratio::new(1, 3), Ratio::new(1, 7));
+        let a = vec![vec![one, one, one], vec![third, seventh, Ratio::from_integer(0)], vec![third, seventh, Ratio::from_integer(0)]];
+        let b = vec![Ratio::from_integer(10), one, Ratio::from_integer(2)];
+        for arithmetic in ["exact", "float"] {
+            assert_eq!(infeasible_rows(a.clone(), b.clone(), vec![one, one, Ratio::from_integer(0)], arithmetic), vec![1, 2]);
+        }
+    }
+}
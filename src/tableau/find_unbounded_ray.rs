@@ -0,0 +1,76 @@
+use crate::tableau::Tableau;
+use crate::certificate::UnboundedCertificate;
+use crate::m::M;
+use crate::scalar::Scalar;
+
+impl<T: Scalar> Tableau<T> {
+    // Builds the certificate for an entering column with no positive entries. Raising the entering variable by t
+    // lowers each basic variable by t times its entry in that column, so the direction is 1 for the entering variable,
+    // the negated column for the basic variables and 0 elsewhere. The point and direction are checked against the
+    // original problem, which fails if an artificial variable would have to change along the ray.
+    pub fn find_unbounded_ray(&self, entering_index: usize) -> UnboundedCertificate<T> {
+        let mut point = vec![T::zero(); self.n];
+        let mut direction = vec![T::zero(); self.n];
+        direction[entering_index] = T::one();
+        for (row, &basis_index) in self.basis_indecies.iter().enumerate() {
+            point[basis_index] = self.b[row].constant();
+            direction[basis_index] = -self.A[row][entering_index];
+        }
+        let n = self.original_c.len().min(self.n);
+        let artificial_unchanged = point.iter().skip(n).chain(direction.iter().skip(n)).all(|el| el.is_zero());
+        point.truncate(n);
+        direction.truncate(n);
+        let point_feasible = point.iter().all(|el| *el >= -self.feasibility_tolerance)
+                             && self.original_A.iter()
+                                               .zip(self.original_b.iter())
+                                               .all(|(row, b)| (row.iter().zip(point.iter()).map(|(a, x)| *a * *x).sum::<T>() - b.constant()).abs() <= self.feasibility_tolerance);
+        let direction_feasible = direction.iter().all(|el| *el >= -self.feasibility_tolerance)
+                                 && self.original_A.iter()
+                                                   .all(|row| row.iter().zip(direction.iter()).map(|(a, d)| *a * *d).sum::<T>().abs() <= self.feasibility_tolerance);
+        let objective_rate: M<T> = direction.iter()
+                                         .zip(self.original_c.iter())
+                                         .map(|(d, c)| c * d)
+                                         .sum();
+        let verified = artificial_unchanged
+                       && point_feasible
+                       && direction_feasible
+                       && objective_rate > M::zero();
+        UnboundedCertificate {
+            entering_index,
+            point,
+            direction,
+            objective_rate,
+            verified,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::tableau::Tableau;
+    use crate::tableau_data::TableauData;
+
+    use num::rational::Ratio;
+
+    #[test]
+    fn verifies_the_ray_of_an_unbounded_problem() {
+        // Maximizes x1 + x2 subject to x1 - x2 <= 1 and x2 - x1 <= 2, which grows without bound along (1, 1).
+        let r = Ratio::from_integer;
+        let a = vec![vec![r(1), r(-1), r(1), r(0)], vec![r(-1), r(1), r(0), r(1)]];
+        let mut t: Tableau = Tableau::new(TableauData::from_standard_form(a, vec![r(1), r(2)], vec![r(1), r(1), r(0), r(0)]));
+        t.DEBUG = false;
+        t.setup();
+        t.solve();
+        assert!(t.error && !t.solved);
+        let certificate = t.unbounded_certificate.clone().unwrap();
+        assert!(certificate.verified);
+        assert_eq!(certificate.entering_index, 1);
+        assert_eq!(certificate.point, [r(1), r(0), r(0), r(3)]);
+        assert_eq!(certificate.direction, [r(1), r(1), r(0), r(0)]);
+        assert_eq!(certificate.objective_rate.to_string(), "2");
+        let mut tampered = t.clone();
+        tampered.original_b[0] = tampered.original_b[1].clone();
+        assert!(!tampered.find_unbounded_ray(1).verified);
+    }
+}
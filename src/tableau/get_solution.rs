@@ -1,7 +1,10 @@
 use crate::tableau::Tableau;
+use crate::m::M;
+use crate::scalar::Scalar;
 
-impl Tableau {
+impl<T: Scalar> Tableau<T> {
     pub fn get_solution(&mut self) {
+        self.solution = vec![M::zero(); self.n];
         for (i, &basis_index) in self.basis_indecies.iter().enumerate() {
             self.solution[basis_index] = self.b[i].clone();
         }
@@ -1,6 +1,7 @@
 use crate::tableau::Tableau;
+use crate::scalar::Scalar;
 
-impl Tableau {
+impl<T: Scalar> Tableau<T> {
     pub fn iterate(&mut self) {
         match self.solve_algorithm.as_str() {
             "standard" => {
@@ -15,6 +16,7 @@ impl Tableau {
                 if self.DEBUG {
                     println!("Entering index: {:?}\nLeaving index: {:?}\n", self.entering_var_index, self.leaving_var_index);
                 }
+                self.explain_step();
                 self.pivot();
             },
             "dual" => {
@@ -26,6 +28,7 @@ impl Tableau {
                 if self.solved || self.error {
                     return;
                 }
+                self.explain_step();
                 self.pivot();
             },
             _ => { 
@@ -1,92 +1,291 @@
 #![allow(non_snake_case)]
 
 mod add_col;
+mod apply_pivot;
 mod calc_reduced_cost;
+mod check_pivot;
+mod end_phase_one;
+mod enumerate_vertices;
+mod explain_step;
+mod find_alternative_solutions;
 mod find_basis_indecies;
+mod find_degenerate_rows;
+mod find_farkas_certificate;
+mod find_infeasible_subset;
+mod find_unbounded_ray;
 mod get_solution;
 mod iterate;
+mod objective_value;
 mod pivot;
+mod record_step;
 mod remove_col;
 mod remove_row;
 mod select_entering_var;
 mod select_leaving_var;
 mod setup;
+mod snap_to_tolerances;
 mod solve;
+mod substitute_m_value;
+mod to_rational;
 mod two_phase_second_phase;
+mod validate_rules;
+mod verify_pivot;
 
+use crate::certificate::{UnboundedCertificate, InfeasibilityCertificate};
 use crate::m::M;
+use crate::scalar::Scalar;
 use crate::tableau_data::TableauData;
 use num::rational::Ratio;
+use serde::{Serialize, Deserialize};
 
-#[derive(Debug)]
-pub struct Tableau {
-    DEBUG: bool,
-    pub A: Vec<Vec<Ratio<i64>>>,
-    pub b: Vec<M>,
-    pub c: Vec<M>,
+// Tolerances used by floating point solves when the request does not set its own. Exact solves always use zero.
+pub const DEFAULT_FEASIBILITY_TOLERANCE: f64 = 1e-9;
+pub const DEFAULT_OPTIMALITY_TOLERANCE: f64 = 1e-9;
+pub const DEFAULT_PIVOT_TOLERANCE: f64 = 1e-9;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Explanation {
+    pub entering_index: usize,
+    pub leaving_index: usize,
+    pub entering_rule: String,
+    pub leaving_rule: String,
+    pub ratios: Vec<Option<String>>,
+    pub text: String,
+    #[serde(default)]
+    pub degenerate: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Step<T: Scalar = Ratio<i64>> {
+    pub A: Vec<Vec<T>>,
+    pub b: Vec<M<T>>,
+    pub reduced_cost: Vec<M<T>>,
+    pub obj: M<T>,
+    pub basis_indecies: Vec<usize>,
+    pub entering_var_index: Option<usize>,
+    pub leaving_var_index: Option<usize>,
+    pub explanation: Option<Explanation>,
+    pub degenerate_pivot: bool,
+    pub degenerate_rows: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Tableau<T: Scalar = Ratio<i64>> {
+    pub DEBUG: bool,
+    pub A: Vec<Vec<T>>,
+    pub b: Vec<M<T>>,
+    pub c: Vec<M<T>>,
     pub m: usize,
     pub n: usize,
-    pub obj: M,
+    pub obj: M<T>,
     pub basis_indecies: Vec<usize>,
-    pub reduced_cost: Vec<M>,
-    two_phase_c: Vec<M>,
+    pub reduced_cost: Vec<M<T>>,
+    two_phase_c: Vec<M<T>>,
     pub has_artificial_vars: bool,
-    variable_select_type: String,
-    solve_algorithm: String,
+    initial_basis_indecies: Vec<usize>,
+    pub variable_select_type: String,
+    pub entering_rule: String,
+    pub leaving_rule: String,
+    pub solve_algorithm: String,
     pub big_M_solve_algorithm: String,
+    pub m_value: T,
+    pub arithmetic: String,
+    pub variable_names: Vec<String>,
+    pub constraint_names: Vec<String>,
+    pub decision_variables: Option<usize>,
+    pub minimize: bool,
+    pub free_variables: Vec<usize>,
+    pub feasibility_tolerance: T,
+    pub optimality_tolerance: T,
+    pub pivot_tolerance: T,
     pub solved: bool,
     pub error: bool, 
     pub error_message: String,
     entering_var_index: Option<usize>,
     leaving_var_index: Option<usize>,
-    pub solution: Vec<M>,
+    explanation: Option<Explanation>,
+    pub solution: Vec<M<T>>,
+    pub steps: Vec<Step<T>>,
+    pub original_A: Vec<Vec<T>>,
+    pub original_b: Vec<M<T>>,
+    pub original_c: Vec<M<T>>,
+    pub alternative_solutions: Vec<Vec<M<T>>>,
+    pub optimal_face: String,
+    pub unbounded_certificate: Option<UnboundedCertificate<T>>,
+    pub infeasibility_certificate: Option<InfeasibilityCertificate<T>>,
+    pub infeasible_rows: Vec<usize>,
 }
 
-impl Tableau {
-    pub fn new(t: TableauData) -> Tableau {
-        let a: Vec<Vec<Ratio<i64>>> = t.A_numerators.into_iter()
-                                                    .zip(t.A_denominators.into_iter())
+// Checks that the arrays a problem is read from fit a tableau with m rows and n columns and have no zero denominators,
+// so that a request missing A, b or c is reported instead of panicking part way through the solve. Costs sent as
+// c_values replace the c arrays, which are then not checked.
+fn problem_error(t: &TableauData) -> Option<String> {
+    let c_dimension_error = if t.c_values.is_empty() {
+        t.c_numerators.len() != t.n || t.c_denominators.len() != t.n || t.c_m_numerators.len() != t.n || t.c_m_denominators.len() != t.n
+    } else {
+        t.c_values.len() != t.n
+    };
+    let dimension_error = if t.A_numerators.len() != t.m || t.A_denominators.len() != t.m
+                             || t.A_numerators.iter().chain(t.A_denominators.iter()).any(|row| row.len() != t.n) {
+        Some("A")
+    } else if t.b_numerators.len() != t.m || t.b_denominators.len() != t.m {
+        Some("b")
+    } else if c_dimension_error {
+        Some("c")
+    } else {
+        None
+    };
+    if let Some(name) = dimension_error {
+        return Some(format!("The dimensions of {} do not match a tableau with {} rows and {} columns.", name, t.m, t.n));
+    }
+    let c_denominators = if t.c_values.is_empty() { &t.c_denominators[..] } else { &[] };
+    let c_m_denominators = if t.c_values.is_empty() { &t.c_m_denominators[..] } else { &[] };
+    let zero_denominator = t.A_denominators.iter()
+                                           .flatten()
+                                           .chain(t.b_denominators.iter())
+                                           .chain(c_denominators.iter())
+                                           .chain(c_m_denominators.iter())
+                                           .any(|&den| den == 0);
+    if zero_denominator {
+        return Some(String::from("Denominators cannot be zero."));
+    }
+    None
+}
+
+impl<T: Scalar> Tableau<T> {
+    pub fn new(mut t: TableauData) -> Tableau<T> {
+        let read_error = t.read_strings().err().or_else(|| problem_error(&t));
+        // A zero denominator has already been reported, so it is read as 0 rather than left to panic.
+        let ratio = |num: i64, den: i64| T::from_ratio(if den == 0 { Ratio::from_integer(0) } else { Ratio::new(num, den) });
+        let a: Vec<Vec<T>> = t.A_numerators.into_iter()
+                                                    .zip(t.A_denominators)
                                                     .map(|(num_row, den_row)| num_row.into_iter()
-                                                                                     .zip(den_row.into_iter())
-                                                                                     .map(|(num, den)| Ratio::new(num, den))
+                                                                                     .zip(den_row)
+                                                                                     .map(|(num, den)| ratio(num, den))
                                                                                      .collect())
                                                     .collect();
-        let b: Vec<M> = t.b_numerators.into_iter()
-                                      .zip(t.b_denominators.into_iter())
-                                      .map(|(num, den)| M::new(Ratio::new(0i64,1), Ratio::new(num, den)))
+        let b: Vec<M<T>> = t.b_numerators.into_iter()
+                                         .zip(t.b_denominators)
+                                         .map(|(num, den)| M::new(T::zero(), ratio(num, den)))
                                       .collect();
-        let c: Vec<M> = t.c_m_numerators.into_iter()
-                                        .zip(t.c_m_denominators.into_iter())
-                                        .zip(t.c_numerators.into_iter()
-                                                           .zip(t.c_denominators.into_iter()))
-                                        .map(|((m_num, m_den), (const_num, const_den))| M::new(Ratio::new(m_num, m_den), Ratio::new(const_num, const_den)))
-                                        .collect();
+        let c: Vec<M<T>> = t.c_m_numerators.into_iter()
+                                           .zip(t.c_m_denominators)
+                                           .zip(t.c_numerators.into_iter()
+                                                              .zip(t.c_denominators))
+                                           .map(|((m_num, m_den), (const_num, const_den))| M::new(ratio(m_num, m_den), ratio(const_num, const_den)))
+                                           .collect();
+        let c = if t.c_values.is_empty() { c } else { t.c_values.iter().map(|el| el.map(|coefficient| T::from_ratio(*coefficient))).collect() };
+        let tolerance = |value: Option<f64>, default: f64| if T::ARITHMETIC == "exact" { T::zero() } else { T::from_f64(value.unwrap_or(default)) };
         Tableau {
             DEBUG: true,
+            original_A: a.clone(),
+            original_b: b.clone(),
+            original_c: c.clone(),
             A: a,
-            b: b,
-            c: c,
+            b,
+            c,
             m: t.m,
             n: t.n,
-            obj: M::new(Ratio::from_integer(0i64), Ratio::from_integer(-1i64)),
+            obj: M::new(T::zero(), -T::one()),
             basis_indecies: vec![t.n;t.m],
             reduced_cost: Vec::with_capacity(t.n),
-            two_phase_c: vec![M::new(Ratio::from_integer(0i64), Ratio::from_integer(0i64));t.n],
+            two_phase_c: vec![M::zero();t.n],
             has_artificial_vars: false,
+            initial_basis_indecies: Vec::with_capacity(t.m),
             variable_select_type: t.variable_select_type,
+            entering_rule: t.entering_rule,
+            leaving_rule: t.leaving_rule,
             solve_algorithm: t.solve_algorithm,
             big_M_solve_algorithm: t.big_M_solve_algorithm,
+            m_value: T::from_ratio(Ratio::from_integer(t.m_value)),
+            arithmetic: String::from(T::ARITHMETIC),
+            variable_names: t.variable_names,
+            constraint_names: t.constraint_names,
+            decision_variables: t.decision_variables,
+            minimize: t.minimize,
+            free_variables: t.free_variables,
+            feasibility_tolerance: tolerance(t.feasibility_tolerance, DEFAULT_FEASIBILITY_TOLERANCE),
+            optimality_tolerance: tolerance(t.optimality_tolerance, DEFAULT_OPTIMALITY_TOLERANCE),
+            pivot_tolerance: tolerance(t.pivot_tolerance, DEFAULT_PIVOT_TOLERANCE),
             solved: false,
-            error: false,
-            error_message: String::from(""),
+            error: read_error.is_some(),
+            error_message: read_error.unwrap_or_default(),
             entering_var_index: None,
             leaving_var_index: None,
-            solution: vec![M::new(Ratio::new(0i64,1), Ratio::new(0i64,1));t.n],
+            explanation: None,
+            solution: vec![M::zero();t.n],
+            steps: Vec::new(),
+            alternative_solutions: Vec::new(),
+            optimal_face: String::from(""),
+            unbounded_certificate: None,
+            infeasibility_certificate: None,
+            infeasible_rows: Vec::new(),
         }
 
     }
 
-    pub fn error_tableau(t: Tableau) -> Tableau {
+}
+
+impl Tableau {
+    // Rebuilds a tableau part way through a solve, keeping the reduced cost, objective and basis that were submitted
+    // instead of deriving them during setup.
+    pub fn restore(mut t: TableauData) -> Result<Tableau, String> {
+        t.read_strings()?;
+        let dimension_error = if t.A_numerators.len() != t.m || t.A_denominators.len() != t.m
+                                 || t.A_numerators.iter().chain(t.A_denominators.iter()).any(|row| row.len() != t.n) {
+            Some("A")
+        } else if t.b_numerators.len() != t.m || t.b_denominators.len() != t.m || t.b_m_numerators.len() != t.m || t.b_m_denominators.len() != t.m {
+            Some("b")
+        } else if t.c_numerators.len() != t.n || t.c_denominators.len() != t.n || t.c_m_numerators.len() != t.n || t.c_m_denominators.len() != t.n {
+            Some("c")
+        } else if t.reduced_cost_numerators.len() != t.n || t.reduced_cost_denominators.len() != t.n || t.reduced_cost_m_numerators.len() != t.n || t.reduced_cost_m_denominators.len() != t.n {
+            Some("the reduced cost")
+        } else if t.basis_indecies.len() != t.m || t.basis_indecies.iter().any(|&index| index >= t.n) {
+            Some("the basis")
+        } else {
+            None
+        };
+        let zero_denominator = t.A_denominators.iter()
+                                               .flatten()
+                                               .chain(t.b_denominators.iter())
+                                               .chain(t.b_m_denominators.iter())
+                                               .chain(t.c_denominators.iter())
+                                               .chain(t.c_m_denominators.iter())
+                                               .chain(t.reduced_cost_denominators.iter())
+                                               .chain(t.reduced_cost_m_denominators.iter())
+                                               .chain([t.obj_denominator, t.obj_m_denominator].iter())
+                                               .any(|&den| den == 0);
+        if let Some(name) = dimension_error {
+            return Err(format!("The dimensions of {} do not match a tableau with {} rows and {} columns.", name, t.m, t.n));
+        }
+        if zero_denominator {
+            return Err(String::from("Denominators cannot be zero."));
+        }
+        let b_m: Vec<Ratio<i64>> = t.b_m_numerators.iter()
+                                                   .zip(t.b_m_denominators.iter())
+                                                   .map(|(&num, &den)| Ratio::new(num, den))
+                                                   .collect();
+        let reduced_cost: Vec<M> = t.reduced_cost_m_numerators.iter()
+                                                              .zip(t.reduced_cost_m_denominators.iter())
+                                                              .zip(t.reduced_cost_numerators.iter()
+                                                                                            .zip(t.reduced_cost_denominators.iter()))
+                                                              .map(|((&m_num, &m_den), (&const_num, &const_den))| M::new(Ratio::new(m_num, m_den), Ratio::new(const_num, const_den)))
+                                                              .collect();
+        let obj = M::new(Ratio::new(t.obj_m_numerator, t.obj_m_denominator), Ratio::new(t.obj_numerator, t.obj_denominator));
+        let basis_indecies = t.basis_indecies.clone();
+        let mut tableau = Tableau::new(t);
+        for (el, m) in tableau.b.iter_mut().zip(b_m) {
+            *el = M::new(m, el.constant());
+        }
+        tableau.reduced_cost = reduced_cost;
+        tableau.obj = obj;
+        tableau.basis_indecies = basis_indecies;
+        Ok(tableau)
+    }
+}
+
+impl<T: Scalar> Tableau<T> {
+    pub fn error_tableau(t: Tableau<T>) -> Tableau<T> {
         Tableau {
             DEBUG: true,
             A: vec![Vec::with_capacity(0);1],
@@ -96,18 +295,41 @@ impl Tableau {
             n: 0,
             reduced_cost: Vec::with_capacity(0),
             two_phase_c: Vec::with_capacity(0),
-            obj: M::new(Ratio::from_integer(0i64), Ratio::from_integer(-1i64)),
+            obj: M::new(T::zero(), -T::one()),
             basis_indecies: Vec::with_capacity(0),
             has_artificial_vars: false,
+            initial_basis_indecies: Vec::with_capacity(0),
             variable_select_type: String::from(""),
+            entering_rule: String::from(""),
+            leaving_rule: String::from(""),
             solve_algorithm: String::from(""),
             big_M_solve_algorithm: String::from(""),
+            m_value: T::zero(),
+            arithmetic: t.arithmetic,
+            variable_names: t.variable_names,
+            constraint_names: t.constraint_names,
+            decision_variables: t.decision_variables,
+            minimize: t.minimize,
+            free_variables: t.free_variables,
+            feasibility_tolerance: t.feasibility_tolerance,
+            optimality_tolerance: t.optimality_tolerance,
+            pivot_tolerance: t.pivot_tolerance,
             solved: false,
             error: t.error,
             error_message: t.error_message,
             entering_var_index: None,
             leaving_var_index: None,
+            explanation: None,
             solution: Vec::with_capacity(0),
+            steps: Vec::with_capacity(0),
+            original_A: Vec::with_capacity(0),
+            original_b: Vec::with_capacity(0),
+            original_c: Vec::with_capacity(0),
+            alternative_solutions: Vec::with_capacity(0),
+            optimal_face: String::from(""),
+            unbounded_certificate: t.unbounded_certificate,
+            infeasibility_certificate: t.infeasibility_certificate,
+            infeasible_rows: t.infeasible_rows,
         }
     }
 
@@ -130,4 +352,53 @@ impl Tableau {
         print!("|\t{}\t", self.obj);
         println!("]\n");
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tableau::Tableau;
+    use crate::tableau_data::TableauData;
+
+    fn request(value: serde_json::Value) -> Tableau {
+        let mut t = Tableau::new(serde_json::from_value::<TableauData>(value).unwrap());
+        t.DEBUG = false;
+        t.setup();
+        t
+    }
+
+    // A request for a problem with one row and two columns, with the given fields added to the solve settings.
+    fn with(fields: serde_json::Value) -> serde_json::Value {
+        let mut value = serde_json::json!({
+            "m": 1, "n": 2, "solve_algorithm": "standard", "variable_select_type": "standard", "big_M_solve_algorithm": "two-phase",
+            "error": false, "error_message": "", "reduced_cost_numerators": [], "reduced_cost_denominators": [],
+            "reduced_cost_m_numerators": [], "reduced_cost_m_denominators": [], "basis_indecies": [], "obj_numerator": 0,
+            "obj_denominator": 1, "obj_m_numerator": 0, "obj_m_denominator": 1, "solved": false, "solution_numerators": [],
+            "solution_denominators": [], "solution_m_numerators": [], "solution_m_denominators": [],
+        });
+        value.as_object_mut().unwrap().extend(fields.as_object().unwrap().clone());
+        value
+    }
+
+    #[test]
+    fn reports_missing_arrays_instead_of_panicking() {
+        let t = request(with(serde_json::json!({ "A": [["1", "1"]], "b": ["2"] })));
+        assert!(t.error && t.error_message.contains("c"), "{}", t.error_message);
+        let t = request(with(serde_json::json!({ "b": ["2"], "c": ["1", "1"] })));
+        assert!(t.error && t.error_message.contains("A"), "{}", t.error_message);
+        let t = request(with(serde_json::json!({ "A": [["1", "1"]], "b_numerators": [2], "b_denominators": [0], "c": ["1", "1"] })));
+        assert!(t.error, "{}", t.error_message);
+        let t = request(with(serde_json::json!({ "A": [["1", "1"]], "b": ["2"], "c": ["1", "1"] })));
+        assert!(!t.error, "{}", t.error_message);
+    }
+
+    #[test]
+    fn accepts_costs_sent_only_as_c_values() {
+        let value = |c_values: serde_json::Value| with(serde_json::json!({ "A": [["1", "1"]], "b": ["2"], "c_values": c_values }));
+        let m1 = serde_json::json!([{ "powers": [1], "numerator": 1, "denominator": 1 }, { "powers": [], "numerator": 3, "denominator": 1 }]);
+        let m2 = serde_json::json!([{ "powers": [0, 1], "numerator": -1, "denominator": 1 }]);
+        let t = request(value(serde_json::json!([m1, m2])));
+        assert!(!t.error, "{}", t.error_message);
+        let t = request(value(serde_json::json!([m1])));
+        assert!(t.error && t.error_message.contains("c"), "{}", t.error_message);
+    }
+}
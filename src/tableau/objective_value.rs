@@ -0,0 +1,11 @@
+use crate::tableau::Tableau;
+use crate::m::M;
+use crate::scalar::Scalar;
+
+impl<T: Scalar> Tableau<T> {
+    // The objective in the problem's own sense. The tableau always maximizes, so a minimization is solved as the
+    // maximization of -z and its value is negated back.
+    pub fn objective_value(&self) -> M<T> {
+        if self.minimize { -&self.obj } else { self.obj.clone() }
+    }
+}
@@ -0,0 +1,28 @@
+use crate::tableau::{Tableau, Step};
+use crate::m::M;
+use crate::scalar::Scalar;
+
+impl<T: Scalar> Tableau<T> {
+    pub fn record_step(&mut self, pivot: Option<(usize, usize)>) {
+        let zero = M::zero();
+        let degenerate_pivot = match pivot {
+            Some((entering_index, leaving_index)) => self.b[leaving_index] == zero || self.reduced_cost[entering_index] == zero,
+            None => false,
+        };
+        self.steps.push(Step {
+            A: self.A.clone(),
+            b: self.b.clone(),
+            reduced_cost: self.reduced_cost.clone(),
+            obj: self.obj.clone(),
+            basis_indecies: self.basis_indecies.clone(),
+            entering_var_index: pivot.map(|(entering_index, _)| entering_index),
+            leaving_var_index: pivot.map(|(_, leaving_index)| leaving_index),
+            explanation: match pivot {
+                Some(_) => self.explanation.take(),
+                None => None,
+            },
+            degenerate_pivot,
+            degenerate_rows: self.find_degenerate_rows(),
+        });
+    }
+}
\ No newline at end of file
@@ -1,6 +1,7 @@
 use crate::tableau::Tableau;
+use crate::scalar::Scalar;
 
-impl Tableau {
+impl<T: Scalar> Tableau<T> {
     pub fn remove_col(&mut self, col_index: usize) {
         for row in self.A.iter_mut() {
             row.remove(col_index);
@@ -1,6 +1,7 @@
 use crate::tableau::Tableau;
+use crate::scalar::Scalar;
 
-impl Tableau {
+impl<T: Scalar> Tableau<T> {
     pub fn remove_row(&mut self, row_index: usize) {
         self.A.remove(row_index);
         self.b.remove(row_index);
@@ -1,28 +1,27 @@
 use crate::tableau::Tableau;
 use crate::m::M;
+use crate::scalar::Scalar;
 
-use num::rational::Ratio;
+use std::cmp::Reverse;
 
-impl Tableau {
+impl<T: Scalar> Tableau<T> {
     pub fn select_entering_var(&mut self) {
         match self.solve_algorithm.as_str() {
             "standard" => {
-                match self.variable_select_type.as_str() {
-                    "standard" => {
+                match self.entering_rule.as_str() {
+                    "dantzig" => {
                         let min_value = self.reduced_cost.iter().min();
                         // match on 3 cases: we have a negative value for our min reduced cost, we have a positive value for our min reduced cost, or we have an empty reduced cost 
                         match min_value {
-                            Some(min_v) if min_v < &M::new(Ratio::new(0i64,1), Ratio::new(0i64,1)) => {
+                            Some(min_v) if min_v < &M::zero() => {
                                 let min_index = self.reduced_cost.iter()
                                                                 .position(|value| value == min_v)
                                                                 .unwrap();
                                 self.entering_var_index = Some(min_index);
-                                return;
                             },
                             Some(_) => {
                                 self.solved = true;
                                 self.entering_var_index = None;
-                                return;
                             }
                             None => {
                                 self.error = true;
@@ -33,28 +32,24 @@ impl Tableau {
                                     self.error_message.push_str("Coefficient matrix is empty. Cannot solve an empty coefficient matrix.");
                                 }
                                 self.entering_var_index = None;
-                                return;
                             }
                         }
                     }, 
                     "bland" => {
-                        match self.reduced_cost.iter().position(|el| el < &M::new(Ratio::from_integer(0i64), Ratio::from_integer(0i64))) {
+                        match self.reduced_cost.iter().position(|el| el < &M::zero()) {
                             Some(index) => {
                                 self.entering_var_index = Some(index);
-                                return;
                             },
                             None => {
                                 self.solved = true;
                                 self.entering_var_index = None;
-                                return;
                             }
                         }
                     },
                     _ => {
                         self.error = true;
-                        self.error_message = String::from("Invalid type for variable selection method");
+                        self.error_message = String::from("Invalid rule for entering variable selection.");
                         self.entering_var_index = None;
-                        return;
                     }
                 }
             }, 
@@ -68,24 +63,21 @@ impl Tableau {
                         return;
                     },
                 };
-                let ratios: Vec<M> = self.A[leaving_index].iter()
+                let ratios: Vec<Option<M<T>>> = self.A[leaving_index].iter()
                                                           .zip(self.reduced_cost.iter())
-                                                          .map(|(a, rc)| if a >= &Ratio::new(0i64,1) { M::new(Ratio::new(0i64,1), Ratio::new(i64::MIN,1)) } else { rc / a })
+                                                          .map(|(a, rc)| if a >= &T::zero() { None } else { Some(rc / a) })
                                                           .collect();
                 let max_value = ratios.iter()
+                                      .flatten()
                                       .max();
-                let max_index = match max_value {
-                    Some(value) if value == &M::new(Ratio::new(0i64,1), Ratio::new(i64::MIN,1)) => {
+                let max_value = match max_value {
+                    Some(value) => value, 
+                    None if !ratios.is_empty() => {
                         self.error = true;
                         self.error_message = String::from("Problem is unbounded.");
                         self.entering_var_index = None;
                         return;
                     },
-                    Some(value) => {
-                        ratios.iter()
-                              .position(|el| el == value)
-                              .unwrap()
-                    }, 
                     None => {
                         self.error = true;
                         self.error_message = String::from("Unknown error. The reduced cost vector seems to be emtpy.");
@@ -93,14 +85,26 @@ impl Tableau {
                         return;
                     }
                 };
-                self.entering_var_index = Some(max_index);
-                return;
+                let ties = ratios.iter()
+                                 .enumerate()
+                                 .filter(|&(_, el)| el.as_ref() == Some(max_value))
+                                 .map(|(index, _)| index);
+                let max_index = match self.entering_rule.as_str() {
+                    "min-ratio" | "bland" => ties.min(),
+                    "largest-pivot" => ties.min_by_key(|&index| Reverse(self.A[leaving_index][index].abs())),
+                    _ => {
+                        self.error = true;
+                        self.error_message = String::from("Invalid rule for entering variable selection.");
+                        self.entering_var_index = None;
+                        return;
+                    }
+                };
+                self.entering_var_index = max_index;
             },
             _ => {
                 self.error = true;
                 self.error_message = String::from("Invalid type for solve algorithm selection");
                 self.entering_var_index = None;
-                return;
             }
         }
     }
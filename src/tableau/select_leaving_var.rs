@@ -1,13 +1,14 @@
 use crate::tableau::Tableau;
 use crate::m::M;
+use crate::scalar::Scalar;
 
-use num::rational::Ratio;
+use std::cmp::Reverse;
 
-impl Tableau {
+impl<T: Scalar> Tableau<T> {
     pub fn select_leaving_var(&mut self) {
         match self.solve_algorithm.as_str() {
             "standard" => {
-                if self.m <= 0 {
+                if self.m == 0 {
                     self.error = true;
                     self.error_message = String::from("Coefficient matrix is empty. Cannot solve an empty coefficient matrix.");
                     self.leaving_var_index = None;
@@ -22,25 +23,23 @@ impl Tableau {
                     },
                     Some(index) => index
                 };
-                let ratios: Vec<M> = self.A.iter()
+                let ratios: Vec<Option<M<T>>> = self.A.iter()
                                            .map(|row| &row[entering_index])
                                            .zip(self.b.iter())
-                                           .map(|(a, b)| if a > &Ratio::from_integer(0i64) { b/a } else { M::new(Ratio::new(i64::MAX,1), Ratio::new(i64::MAX, 1)) })
+                                           .map(|(a, b)| if a > &T::zero() { Some(b/a) } else { None })
                                            .collect();
                 let min_ratio = ratios.iter()
+                                      .flatten()
                                       .min();
-                let min_index = match min_ratio {
-                    Some(ratio) if ratio == &M::new(Ratio::new(i64::MAX,1), Ratio::new(i64::MAX, 1)) => {
+                let min_ratio = match min_ratio {
+                    Some(ratio) => ratio,
+                    None if !ratios.is_empty() => {
                         self.error = true;
                         self.error_message = String::from("Problem is unbounded.");
+                        self.unbounded_certificate = Some(self.find_unbounded_ray(entering_index));
                         self.leaving_var_index = None;
                         return;
                     },
-                    Some(ratio) => {
-                        ratios.iter()
-                              .position(|el| el == ratio)
-                              .unwrap()
-                    },
                     None => {
                         self.error = true;
                         self.error_message = String::from("Unknown error has occurred. It seems like b is empty.");
@@ -48,17 +47,34 @@ impl Tableau {
                         return;
                     }
                 };
-                self.leaving_var_index = Some(min_index);
-                return;
+                let ties = ratios.iter()
+                                 .enumerate()
+                                 .filter(|&(_, el)| el.as_ref() == Some(min_ratio))
+                                 .map(|(index, _)| index);
+                let min_index = match self.leaving_rule.as_str() {
+                    "min-ratio" => ties.min(),
+                    "bland" => ties.min_by_key(|&index| self.basis_indecies[index]),
+                    "largest-pivot" => ties.min_by_key(|&index| Reverse(self.A[index][entering_index])),
+                    "lexicographic" => ties.min_by_key(|&index| self.A[index].iter()
+                                                                                .map(|el| *el / self.A[index][entering_index])
+                                                                                .collect::<Vec<T>>()),
+                    _ => {
+                        self.error = true;
+                        self.error_message = String::from("Invalid rule for leaving variable selection.");
+                        self.leaving_var_index = None;
+                        return;
+                    }
+                };
+                self.leaving_var_index = min_index;
             },
             "dual" => {
-                match self.variable_select_type.as_str() {
-                    "standard" => {
+                match self.leaving_rule.as_str() {
+                    "dantzig" => {
                         let min_value = self.b.iter()
                                               .min();
                         match min_value {
                             Some(value) => {
-                                if value >= &M::new(Ratio::new(0i64,1), Ratio::new(0i64,1)) {
+                                if value >= &M::zero() {
                                     self.solved = true;
                                     self.leaving_var_index = None;
                                     return;
@@ -67,34 +83,29 @@ impl Tableau {
                                                       .position(|el| el == value)
                                                       .unwrap();
                                 self.leaving_var_index = Some(min_index);
-                                return;
                             }, 
                             None => {
                                 self.error = true;
                                 self.error_message = String::from("Unknown error. It seems like b is empty.");
                                 self.leaving_var_index = None;
-                                return;
                             }
                         }
                     }, 
                     "bland" => {
-                        match self.b.iter().position(|el| el < &M::new(Ratio::new(0i64,1), Ratio::new(0i64,1))) {
+                        match self.b.iter().position(|el| el < &M::zero()) {
                             Some(index) => {
                                 self.leaving_var_index = Some(index);
-                                return;
                             },
                             None => {
                                 self.solved = true;
                                 self.leaving_var_index = None;
-                                return;
                             }
                         }
                     },
                     _ => {
                         self.error = true;
-                        self.error_message = String::from("Invalid variable select type.");
+                        self.error_message = String::from("Invalid rule for leaving variable selection.");
                         self.leaving_var_index = None;
-                        return;
                     }
                 }
             },
@@ -102,7 +113,6 @@ impl Tableau {
                 self.error = true;
                 self.error_message = String::from("Invalid type for solve algorithm selection");
                 self.leaving_var_index = None;
-                return;
             }
         }
     }
@@ -1,18 +1,31 @@
 use crate::tableau::Tableau;
+use crate::certificate::InfeasibilityCertificate;
 use crate::m::M;
-
-use num::rational::Ratio;
+use crate::scalar::Scalar;
 
 struct FuturePivot {
     entering_index: usize,
     leaving_index: usize,
 }
 
-impl Tableau {
+impl<T: Scalar> Tableau<T> {
     pub fn setup(&mut self) {
+        if self.error {
+            return;
+        }
+        self.validate_rules();
+        if self.error {
+            return;
+        }
+        if self.big_M_solve_algorithm.as_str() == "numeric" {
+            self.substitute_m_value();
+            if self.error {
+                return;
+            }
+        }
         match self.solve_algorithm.as_str() {
             "standard" => {
-                if self.b.iter().find(|&el| el < &M::new(Ratio::new(0i64,1), Ratio::new(0i64,1))).is_some() { 
+                if self.b.iter().find(|&el| el < &M::zero()).is_some() { 
                     self.error = true;
                     self.error_message = String::from("Cannot have negative values for b. Please multiply any rows with a negative b value by -1.");
                     return;
@@ -25,21 +38,24 @@ impl Tableau {
                 self.reduced_cost = self.c.iter()
                                           .map(|c| -c)
                                           .collect();
-                self.obj = M::new(Ratio::new(0i64,1), Ratio::new(0i64,1));
+                self.obj = M::zero();
                 let mut future_pivots: Vec<FuturePivot> = Vec::with_capacity(self.m);
                 let mut rows_to_remove: Vec<usize> = Vec::with_capacity(self.m);
                 for (row, row_index) in self.A.iter().zip(0..self.m) {
                     if self.basis_indecies[row_index] == self.n {
                         let entering_index = row.iter()
-                                                .position(|el| el != &Ratio::new(0i64,1));
+                                                .position(|el| el != &T::zero());
                         match entering_index {
                             Some(index) => {
                                 future_pivots.push(FuturePivot { entering_index: index, leaving_index: row_index });
                             }, 
                             None => {
-                                if self.b[row_index] != M::new(Ratio::new(0i64,1), Ratio::new(0i64,1)) {
+                                if self.b[row_index] != M::zero() {
                                     self.error = true; 
                                     self.error_message = format!("Cannot satisfy the {}th constraint. Problem is infeasible.", row_index);
+                                    let mut multipliers = vec![T::zero(); self.m];
+                                    multipliers[row_index] = if self.b[row_index] > M::zero() { -T::one() } else { T::one() };
+                                    self.infeasibility_certificate = Some(InfeasibilityCertificate::new(multipliers, &self.original_A, &self.original_b, self.feasibility_tolerance));
                                     return;
                                 } else {
                                     rows_to_remove.push(row_index);
@@ -63,22 +79,22 @@ impl Tableau {
                     self.pivot();
                 }
                 self.calc_reduced_cost();
-                if self.reduced_cost.iter().find(|&el| el < &M::new(Ratio::new(0i64,1), Ratio::new(0i64,1))).is_none() {
+                if self.reduced_cost.iter().find(|&el| el < &M::zero()).is_none() {
                     return;
                 }
-                self.A.push(vec![Ratio::new(1i64,1);self.n]);
+                self.A.push(vec![T::one();self.n]);
                 for &index in &self.basis_indecies {
-                    self.A[self.m][index] = Ratio::new(0i64,1);
+                    self.A[self.m][index] = T::zero();
                 }
                 if self.DEBUG {
                     self.print_table();
                 }
                 self.m += 1;
                 self.basis_indecies.push(self.m);
-                self.b.push(M::new(Ratio::new(1i64,1), Ratio::new(0i64,1)));
-                self.solution.push(M::new(Ratio::new(0i64,1), Ratio::new(-1i64,1)));
-                self.add_col(self.m - 1, M::new(Ratio::new(0i64,1), Ratio::new(0i64,1)));
-                self.reduced_cost.push(M::new(Ratio::new(0i64,1), Ratio::new(0i64,1)));
+                self.b.push(M::new(T::one(), T::zero()));
+                self.solution.push(M::new(T::zero(), -T::one()));
+                self.add_col(self.m - 1, M::zero());
+                self.reduced_cost.push(M::zero());
                 if self.DEBUG {
                     self.print_table();
                 }
@@ -92,12 +108,10 @@ impl Tableau {
                 self.entering_var_index = self.reduced_cost.iter()
                                                            .position(|el| el == min_value);
                 self.pivot();
-                return;
             },
             _ => {
                 self.error = true; 
                 self.error_message = String::from("Unknown selection for solve algorithm.");
-                return;
             }
         }
     }
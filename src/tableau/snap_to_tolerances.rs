@@ -0,0 +1,25 @@
+use crate::tableau::Tableau;
+use crate::scalar::Scalar;
+
+impl<T: Scalar> Tableau<T> {
+    // Floating point pivots leave tiny values where exact arithmetic would give 0. Anything within the tolerances is
+    // set back to 0 so that it is not picked as a pivot, read as a negative reduced cost or reported as infeasible.
+    // Exact solves use tolerances of 0, which leaves every value as it is.
+    pub fn snap_to_tolerances(&mut self) {
+        if self.pivot_tolerance.is_zero() && self.feasibility_tolerance.is_zero() && self.optimality_tolerance.is_zero() {
+            return;
+        }
+        for el in self.A.iter_mut().flatten() {
+            if el.abs() <= self.pivot_tolerance {
+                *el = T::zero();
+            }
+        }
+        self.b = self.b.iter()
+                       .map(|el| el.snap(&self.feasibility_tolerance))
+                       .collect();
+        self.reduced_cost = self.reduced_cost.iter()
+                                             .map(|el| el.snap(&self.optimality_tolerance))
+                                             .collect();
+        self.obj = self.obj.snap(&self.feasibility_tolerance);
+    }
+}
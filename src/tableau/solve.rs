@@ -1,13 +1,18 @@
 use crate::tableau::Tableau;
+use crate::scalar::Scalar;
 
-impl Tableau {
+impl<T: Scalar> Tableau<T> {
     pub fn solve(&mut self) {
         for _ in 0..self.n + 1 {
-            if self.solved || self.error { return; }
+            if self.solved || self.error { break; }
             if self.DEBUG {
                 self.print_table();
             }
             self.iterate();
         }
+        self.record_step(None);
+        if self.solved && self.big_M_solve_algorithm.as_str() == "numeric" {
+            self.check_m_value();
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,80 @@
+use crate::tableau::Tableau;
+use crate::m::M;
+use crate::scalar::Scalar;
+
+impl<T: Scalar> Tableau<T> {
+    // Numeric big-M: M is replaced by the number the user chose, so every cost and every value in the trace is a
+    // plain rational that can be compared with a hand calculation. Artificial variables are given the cost -m_value
+    // when they are added in add_col.
+    pub fn substitute_m_value(&mut self) {
+        if self.m_value <= T::zero() {
+            self.error = true;
+            self.error_message = String::from("Numeric big-M needs a positive m_value.");
+            return;
+        }
+        if self.c.iter().any(|c| c.levels() > 1) {
+            self.error = true;
+            self.error_message = String::from("Numeric big-M only supports a single level of M.");
+            return;
+        }
+        self.c = self.c.iter()
+                       .map(|c| M::new(T::zero(), c.evaluate(&self.m_value)))
+                       .collect();
+    }
+
+    // A numeric M that is too small can make keeping an artificial variable in the basis cheaper than satisfying the
+    // row it stands in for, in which case the optimum found is not a solution of the original problem.
+    pub fn check_m_value(&mut self) {
+        let n = self.original_c.len();
+        let artificial = (0..self.m).find(|&row| self.basis_indecies[row] >= n && !self.b[row].snap(&self.feasibility_tolerance).is_zero());
+        if let Some(row) = artificial {
+            self.solved = false;
+            self.error = true;
+            self.error_message = format!("The artificial variable x{} is still basic with value {} at the optimum, so either the m_value {} is too small or the underlying LP is infeasible. Try a larger m_value.",
+                                         self.basis_indecies[row] + 1, self.b[row], self.m_value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tableau::Tableau;
+    use crate::tableau_data::TableauData;
+
+    use num::rational::Ratio;
+
+    // Minimizes 10x1 + 10x2 subject to x1 + x2 = 2 and x1 - x2 = 0, where both rows need an artificial variable.
+    fn solve(m_value: i64) -> Tableau {
+        let r = Ratio::from_integer;
+        let mut data = TableauData::from_standard_form(vec![vec![r(1), r(1)], vec![r(1), r(-1)]], vec![r(2), r(0)], vec![r(-10), r(-10)]);
+        data.big_M_solve_algorithm = String::from("numeric");
+        data.m_value = m_value;
+        let mut t = Tableau::new(data);
+        t.DEBUG = false;
+        t.setup();
+        t.solve();
+        t
+    }
+
+    #[test]
+    fn solves_with_a_large_enough_m_value() {
+        let mut t = solve(100);
+        assert!(t.solved && !t.error, "{}", t.error_message);
+        t.get_solution();
+        let values: Vec<String> = t.solution[..2].iter().map(|el| el.to_string()).collect();
+        assert_eq!(values, ["1", "1"]);
+    }
+
+    #[test]
+    fn detects_an_m_value_that_is_too_small() {
+        let t = solve(5);
+        assert!(t.error && !t.solved);
+        assert!(t.error_message.contains("m_value 5 is too small"), "{}", t.error_message);
+    }
+
+    #[test]
+    fn rejects_a_non_positive_m_value() {
+        let t = solve(0);
+        assert!(t.error && t.error_message.contains("positive m_value"), "{}", t.error_message);
+    }
+}
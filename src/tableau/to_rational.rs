@@ -0,0 +1,85 @@
+use crate::tableau::{Tableau, Step};
+use crate::certificate::{UnboundedCertificate, InfeasibilityCertificate};
+use crate::m::M;
+use crate::scalar::Scalar;
+
+use num::rational::Ratio;
+
+impl<T: Scalar> Tableau<T> {
+    // Converts a tableau solved in any arithmetic back to rationals, so that the response, the report and the pivot
+    // checks only ever have to deal with one kind of tableau. Floating point values become the rational their decimal
+    // form is, and a value that is not finite or does not fit in a rational is an error rather than a wrong answer.
+    pub fn to_rational(&self) -> Result<Tableau, String> {
+        let row = |values: &Vec<T>| values.iter().map(|el| el.to_ratio()).collect::<Result<Vec<Ratio<i64>>, String>>();
+        let matrix = |values: &Vec<Vec<T>>| values.iter().map(row).collect::<Result<Vec<Vec<Ratio<i64>>>, String>>();
+        let m = |value: &M<T>| value.try_map(|el| el.to_ratio());
+        let ms = |values: &Vec<M<T>>| values.iter().map(m).collect::<Result<Vec<M>, String>>();
+        Ok(Tableau {
+            DEBUG: self.DEBUG,
+            A: matrix(&self.A)?,
+            b: ms(&self.b)?,
+            c: ms(&self.c)?,
+            m: self.m,
+            n: self.n,
+            obj: m(&self.obj)?,
+            basis_indecies: self.basis_indecies.clone(),
+            reduced_cost: ms(&self.reduced_cost)?,
+            two_phase_c: ms(&self.two_phase_c)?,
+            has_artificial_vars: self.has_artificial_vars,
+            initial_basis_indecies: self.initial_basis_indecies.clone(),
+            variable_select_type: self.variable_select_type.clone(),
+            entering_rule: self.entering_rule.clone(),
+            leaving_rule: self.leaving_rule.clone(),
+            solve_algorithm: self.solve_algorithm.clone(),
+            big_M_solve_algorithm: self.big_M_solve_algorithm.clone(),
+            m_value: self.m_value.to_ratio()?,
+            arithmetic: self.arithmetic.clone(),
+            variable_names: self.variable_names.clone(),
+            constraint_names: self.constraint_names.clone(),
+            decision_variables: self.decision_variables,
+            minimize: self.minimize,
+            free_variables: self.free_variables.clone(),
+            feasibility_tolerance: self.feasibility_tolerance.to_ratio()?,
+            optimality_tolerance: self.optimality_tolerance.to_ratio()?,
+            pivot_tolerance: self.pivot_tolerance.to_ratio()?,
+            solved: self.solved,
+            error: self.error,
+            error_message: self.error_message.clone(),
+            entering_var_index: self.entering_var_index,
+            leaving_var_index: self.leaving_var_index,
+            explanation: self.explanation.clone(),
+            solution: ms(&self.solution)?,
+            steps: self.steps.iter()
+                             .map(|step| Ok(Step {
+                                 A: matrix(&step.A)?,
+                                 b: ms(&step.b)?,
+                                 reduced_cost: ms(&step.reduced_cost)?,
+                                 obj: m(&step.obj)?,
+                                 basis_indecies: step.basis_indecies.clone(),
+                                 entering_var_index: step.entering_var_index,
+                                 leaving_var_index: step.leaving_var_index,
+                                 explanation: step.explanation.clone(),
+                                 degenerate_pivot: step.degenerate_pivot,
+                                 degenerate_rows: step.degenerate_rows.clone(),
+                             }))
+                             .collect::<Result<Vec<Step>, String>>()?,
+            original_A: matrix(&self.original_A)?,
+            original_b: ms(&self.original_b)?,
+            original_c: ms(&self.original_c)?,
+            alternative_solutions: self.alternative_solutions.iter().map(ms).collect::<Result<Vec<Vec<M>>, String>>()?,
+            optimal_face: self.optimal_face.clone(),
+            unbounded_certificate: self.unbounded_certificate.as_ref().map(|certificate| Ok::<_, String>(UnboundedCertificate {
+                entering_index: certificate.entering_index,
+                point: row(&certificate.point)?,
+                direction: row(&certificate.direction)?,
+                objective_rate: m(&certificate.objective_rate)?,
+                verified: certificate.verified,
+            })).transpose()?,
+            infeasibility_certificate: self.infeasibility_certificate.as_ref().map(|certificate| Ok::<_, String>(InfeasibilityCertificate {
+                multipliers: row(&certificate.multipliers)?,
+                verified: certificate.verified,
+            })).transpose()?,
+            infeasible_rows: self.infeasible_rows.clone(),
+        })
+    }
+}
@@ -0,0 +1,59 @@
+use crate::tableau::Tableau;
+use crate::scalar::Scalar;
+
+const PRICING_RULES: [&str; 2] = ["dantzig", "bland"];
+const PRIMAL_RATIO_RULES: [&str; 4] = ["min-ratio", "bland", "lexicographic", "largest-pivot"];
+const DUAL_RATIO_RULES: [&str; 3] = ["min-ratio", "bland", "largest-pivot"];
+
+impl<T: Scalar> Tableau<T> {
+    // Fills in any rule that was left empty from the legacy variable_select_type, then rejects rule combinations
+    // that the selected solve algorithm does not support before any work is done on the tableau.
+    pub fn validate_rules(&mut self) {
+        let legacy_rule = match self.variable_select_type.as_str() {
+            "standard" => "dantzig",
+            other => other,
+        };
+        let (pricing_rule, ratio_rule, ratio_rules) = match self.solve_algorithm.as_str() {
+            "standard" => {
+                if self.entering_rule.is_empty() {
+                    self.entering_rule = String::from(legacy_rule);
+                }
+                if self.leaving_rule.is_empty() {
+                    self.leaving_rule = String::from("min-ratio");
+                }
+                (&self.entering_rule, &self.leaving_rule, &PRIMAL_RATIO_RULES[..])
+            },
+            "dual" => {
+                if self.leaving_rule.is_empty() {
+                    self.leaving_rule = String::from(legacy_rule);
+                }
+                if self.entering_rule.is_empty() {
+                    self.entering_rule = String::from("min-ratio");
+                }
+                (&self.leaving_rule, &self.entering_rule, &DUAL_RATIO_RULES[..])
+            },
+            _ => {
+                self.error = true;
+                self.error_message = String::from("Unknown selection for solve algorithm.");
+                return;
+            }
+        };
+        if !PRICING_RULES.contains(&pricing_rule.as_str()) {
+            self.error = true;
+            self.error_message = format!("Unsupported rule '{}' for choosing the {} variable with the {} algorithm. Supported rules are: {}.",
+                                         pricing_rule,
+                                         if self.solve_algorithm.as_str() == "standard" { "entering" } else { "leaving" },
+                                         self.solve_algorithm,
+                                         PRICING_RULES.join(", "));
+            return;
+        }
+        if !ratio_rules.contains(&ratio_rule.as_str()) {
+            self.error = true;
+            self.error_message = format!("Unsupported rule '{}' for choosing the {} variable with the {} algorithm. Supported rules are: {}.",
+                                         ratio_rule,
+                                         if self.solve_algorithm.as_str() == "standard" { "leaving" } else { "entering" },
+                                         self.solve_algorithm,
+                                         ratio_rules.join(", "));
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,122 @@
+use crate::tableau::Tableau;
+use crate::verification::{CellMistake, VerificationData};
+
+impl Tableau {
+    // Works out which pivot takes this tableau to the submitted one from the change in basis, then compares
+    // every cell of the submitted tableau against the result of actually making that pivot.
+    pub fn verify_pivot(&self, next: &Tableau) -> VerificationData {
+        if next.m != self.m || next.n != self.n {
+            return VerificationData::error(format!("The next tableau has {} rows and {} columns but the previous tableau has {} rows and {} columns.", next.m, next.n, self.m, self.n));
+        }
+        let changed_rows: Vec<usize> = (0..self.m).filter(|&row| self.basis_indecies[row] != next.basis_indecies[row])
+                                                  .collect();
+        let leaving_index = match changed_rows.as_slice() {
+            [row] => *row,
+            [] => {
+                return VerificationData::error(String::from("The basis is the same in both tableaus, so no pivot could be identified."));
+            },
+            _ => {
+                return VerificationData::error(format!("The basis changed in {} rows, but a single pivot only changes the basis in one row.", changed_rows.len()));
+            }
+        };
+        let entering_index = next.basis_indecies[leaving_index];
+        let mut expected = self.clone();
+        expected.apply_pivot(entering_index, leaving_index);
+        if expected.error {
+            return VerificationData::error(expected.error_message);
+        }
+        let mut mistakes: Vec<CellMistake> = Vec::new();
+        for row in 0..self.m {
+            for col in 0..self.n {
+                if expected.A[row][col] != next.A[row][col] {
+                    mistakes.push(CellMistake {
+                        location: String::from("A"),
+                        row: Some(row),
+                        col: Some(col),
+                        expected: expected.A[row][col].to_string(),
+                        submitted: next.A[row][col].to_string(),
+                    });
+                }
+            }
+            if expected.b[row] != next.b[row] {
+                mistakes.push(CellMistake {
+                    location: String::from("b"),
+                    row: Some(row),
+                    col: None,
+                    expected: expected.b[row].to_string(),
+                    submitted: next.b[row].to_string(),
+                });
+            }
+        }
+        for col in 0..self.n {
+            if expected.reduced_cost[col] != next.reduced_cost[col] {
+                mistakes.push(CellMistake {
+                    location: String::from("reduced_cost"),
+                    row: None,
+                    col: Some(col),
+                    expected: expected.reduced_cost[col].to_string(),
+                    submitted: next.reduced_cost[col].to_string(),
+                });
+            }
+        }
+        if expected.obj != next.obj {
+            mistakes.push(CellMistake {
+                location: String::from("obj"),
+                row: None,
+                col: None,
+                expected: expected.obj.to_string(),
+                submitted: next.obj.to_string(),
+            });
+        }
+        VerificationData {
+            entering_index: Some(entering_index),
+            leaving_index: Some(leaving_index),
+            correct: mistakes.is_empty(),
+            mistakes,
+            error: false,
+            error_message: String::from(""),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tableau::Tableau;
+    use crate::tableau_data::TableauData;
+
+    use num::rational::Ratio;
+
+    // Maximizes 3x1 + 2x2 subject to x1 + x2 <= 4 and x1 + 3x2 <= 6.
+    fn tableau() -> Tableau {
+        let r = Ratio::from_integer;
+        let a = vec![vec![r(1), r(1), r(1), r(0)], vec![r(1), r(3), r(0), r(1)]];
+        let mut t = Tableau::new(TableauData::from_standard_form(a, vec![r(4), r(6)], vec![r(3), r(2), r(0), r(0)]));
+        t.DEBUG = false;
+        t.setup();
+        t
+    }
+
+    #[test]
+    fn accepts_a_correct_pivot() {
+        let previous = tableau();
+        let mut next = previous.clone();
+        next.apply_pivot(0, 0);
+        let verification = previous.verify_pivot(&next);
+        assert!(verification.correct && !verification.error, "{:?}", verification);
+        assert_eq!((verification.entering_index, verification.leaving_index), (Some(0), Some(0)));
+    }
+
+    #[test]
+    fn flags_a_wrong_cell() {
+        let previous = tableau();
+        let mut next = previous.clone();
+        next.apply_pivot(0, 0);
+        next.A[1][1] = Ratio::from_integer(3);
+        let verification = previous.verify_pivot(&next);
+        assert!(!verification.correct && !verification.error);
+        assert_eq!(verification.mistakes.len(), 1);
+        let mistake = &verification.mistakes[0];
+        assert_eq!((mistake.location.as_str(), mistake.row, mistake.col), ("A", Some(1), Some(1)));
+        assert_eq!((mistake.expected.as_str(), mistake.submitted.as_str()), ("2", "3"));
+    }
+}
\ No newline at end of file
@@ -1,94 +1,329 @@
 #![allow(non_snake_case)]
 
 use crate::Tableau;
+use crate::certificate::{UnboundedCertificateData, InfeasibilityCertificateData};
+use crate::tableau::Explanation;
+use crate::m::M;
+use crate::parse::{parse_ratio, parse_m};
+use crate::scalar::Scalar;
+use num::rational::Ratio;
 use serde::{Serialize, Deserialize};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct TableauData {
+    #[serde(default)]
+    pub A: Vec<Vec<String>>,
+    #[serde(default)]
+    pub b: Vec<String>,
+    #[serde(default)]
+    pub c: Vec<String>,
+    #[serde(default)]
     pub A_numerators: Vec<Vec<i64>>,
+    #[serde(default)]
     pub A_denominators: Vec<Vec<i64>>,
+    #[serde(default)]
     pub b_numerators: Vec<i64>,
+    #[serde(default)]
     pub b_denominators: Vec<i64>,
-    b_m_numerators: Vec<i64>,
-    b_m_denominators: Vec<i64>,
+    #[serde(default)]
+    pub b_m_numerators: Vec<i64>,
+    #[serde(default)]
+    pub b_m_denominators: Vec<i64>,
+    #[serde(default)]
     pub c_numerators: Vec<i64>,
+    #[serde(default)]
     pub c_denominators: Vec<i64>,
+    #[serde(default)]
     pub c_m_numerators: Vec<i64>,
+    #[serde(default)]
     pub c_m_denominators: Vec<i64>,
+    #[serde(default)]
+    pub c_values: Vec<M>,
     pub m: usize,
     pub n: usize,
     pub solve_algorithm: String,
     pub variable_select_type: String,
+    #[serde(default)]
+    pub entering_rule: String,
+    #[serde(default)]
+    pub leaving_rule: String,
     pub big_M_solve_algorithm: String,
+    #[serde(default)]
+    pub m_value: i64,
+    #[serde(default)]
+    pub variable_names: Vec<String>,
+    #[serde(default)]
+    pub constraint_names: Vec<String>,
+    // The number of leading columns that are decision variables, when the problem says so. Every later column is a
+    // slack or surplus column, which lets the plots read each row back as an inequality.
+    #[serde(default)]
+    pub decision_variables: Option<usize>,
+    // Set when the problem minimizes. The tableau always maximizes, so c holds the negated costs and the objective is
+    // negated back wherever it is reported as the problem's value.
+    #[serde(default)]
+    pub minimize: bool,
+    // The columns that hold the positive part of a free variable, whose negative part is the column after it. The
+    // variable's value is reported as the difference of the two.
+    #[serde(default)]
+    pub free_variables: Vec<usize>,
+    #[serde(default)]
+    pub arithmetic: String,
+    #[serde(default)]
+    pub feasibility_tolerance: Option<f64>,
+    #[serde(default)]
+    pub optimality_tolerance: Option<f64>,
+    #[serde(default)]
+    pub pivot_tolerance: Option<f64>,
     error: bool,
     error_message: String,
-    reduced_cost_numerators: Vec<i64>,
-    reduced_cost_denominators: Vec<i64>,
-    reduced_cost_m_numerators: Vec<i64>,
-    reduced_cost_m_denominators: Vec<i64>,
-    basis_indecies: Vec<usize>,
-    obj_numerator: i64,
-    obj_denominator: i64,
-    obj_m_numerator: i64,
-    obj_m_denominator: i64,
+    pub reduced_cost_numerators: Vec<i64>,
+    pub reduced_cost_denominators: Vec<i64>,
+    pub reduced_cost_m_numerators: Vec<i64>,
+    pub reduced_cost_m_denominators: Vec<i64>,
+    pub basis_indecies: Vec<usize>,
+    pub obj_numerator: i64,
+    pub obj_denominator: i64,
+    pub obj_m_numerator: i64,
+    pub obj_m_denominator: i64,
     solved: bool,
     solution_numerators: Vec<i64>,
     solution_denominators: Vec<i64>,
     solution_m_numerators: Vec<i64>,
     solution_m_denominators: Vec<i64>,
+    #[serde(default)]
+    explanations: Vec<Explanation>,
+    #[serde(default)]
+    alternative_solution_numerators: Vec<Vec<i64>>,
+    #[serde(default)]
+    alternative_solution_denominators: Vec<Vec<i64>>,
+    #[serde(default)]
+    alternative_solution_m_numerators: Vec<Vec<i64>>,
+    #[serde(default)]
+    alternative_solution_m_denominators: Vec<Vec<i64>>,
+    #[serde(default)]
+    optimal_face: String,
+    #[serde(default)]
+    unbounded_certificate: Option<UnboundedCertificateData>,
+    #[serde(default)]
+    infeasibility_certificate: Option<InfeasibilityCertificateData>,
+    #[serde(default)]
+    infeasible_rows: Vec<usize>,
+    #[serde(default)]
+    degenerate_steps: Vec<usize>,
+    #[serde(default)]
+    degenerate_pivot_count: usize,
+    #[serde(default)]
+    degenerate_optimal: bool,
+    #[serde(default)]
+    b_values: Vec<M>,
+    #[serde(default)]
+    reduced_cost_values: Vec<M>,
+    #[serde(default)]
+    obj_value: M,
+    // The objective in the problem's own sense, which differs from obj_value only for a minimization.
+    #[serde(default)]
+    objective_value: M,
+    #[serde(default)]
+    solution_values: Vec<M>,
+    // The same results written as decimals, filled in only for float arithmetic, where the fractions above are just
+    // the decimals the solver worked with written as fractions.
+    #[serde(default)]
+    b_decimals: Vec<String>,
+    #[serde(default)]
+    reduced_cost_decimals: Vec<String>,
+    #[serde(default)]
+    obj_decimal: String,
+    #[serde(default)]
+    solution_decimals: Vec<String>,
+    #[serde(default)]
+    alternative_solution_decimals: Vec<Vec<String>>,
+}
+
+// The numerators and denominators of the constant and M parts of a list of values, as the response arrays send them.
+struct Parts {
+    numerators: Vec<i64>,
+    denominators: Vec<i64>,
+    m_numerators: Vec<i64>,
+    m_denominators: Vec<i64>,
+}
+
+impl Parts {
+    fn new(values: &[M]) -> Parts {
+        Parts {
+            numerators: values.iter().map(|el| *el.constant().numer()).collect(),
+            denominators: values.iter().map(|el| *el.constant().denom()).collect(),
+            m_numerators: values.iter().map(|el| *el.m().numer()).collect(),
+            m_denominators: values.iter().map(|el| *el.m().denom()).collect(),
+        }
+    }
 }
 
 impl TableauData {
     pub fn new(t: Tableau) -> TableauData {
-        let (a_num, a_den): (Vec<Vec<i64>>, Vec<Vec<i64>>) = t.A.into_iter()
-                                                                .map(|row| row.into_iter()
-                                                                              .map(|el| (*el.numer(), *el.denom()))
-                                                                              .unzip())
-                                                                .unzip();
-        let ((b_num, b_den), (b_m_num, b_m_den)): ((Vec<i64>, Vec<i64>), (Vec<i64>, Vec<i64>)) = t.b.into_iter()
-                                                                                                    .map(|el| ((*el.constant.numer(), *el.constant.denom()), (*el.M.numer(), *el.M.denom())))
-                                                                                                    .unzip();
-        let ((c_num, c_den), (c_m_num, c_m_den)): ((Vec<i64>, Vec<i64>),(Vec<i64>, Vec<i64>)) = t.c.into_iter()
-                                                                                                    .map(|el| ((*el.constant.numer(), *el.constant.denom()), (*el.M.numer(), *el.M.denom())))
-                                                                                                    .unzip();
-        let ((reduced_cost_num, reduced_cost_den), (reduced_cost_m_num, reduced_cost_m_den)): ((Vec<i64>, Vec<i64>), (Vec<i64>, Vec<i64>)) = t.reduced_cost.into_iter()
-                                                                                                                               .map(|el| ((*el.constant.numer(), *el.constant.denom()), (*el.M.numer(), *el.M.denom())))
-                                                                                                                               .unzip();
-        let ((sol_num, sol_den), (sol_m_num, sol_m_den)): ((Vec<i64>, Vec<i64>), (Vec<i64>, Vec<i64>)) = t.solution.into_iter()
-                                                                                                                   .map(|el| ((*el.constant.numer(), *el.constant.denom()), (*el.M.numer(), *el.M.denom())))
-                                                                                                                   .unzip();
+        let degenerate_steps: Vec<usize> = t.steps.iter()
+                                                  .enumerate()
+                                                  .filter(|(_, step)| step.degenerate_pivot)
+                                                  .map(|(index, _)| index)
+                                                  .collect();
+        let degenerate_optimal = t.solved && !t.find_degenerate_rows().is_empty();
+        let b_values = t.b.clone();
+        let c_values = t.c.clone();
+        let reduced_cost_values = t.reduced_cost.clone();
+        let solution_values = t.solution.clone();
+        let decimal = t.arithmetic.as_str() == "float";
+        let decimals = |values: &[M]| if decimal { values.iter().map(M::to_decimal).collect() } else { Vec::new() };
+        let (b_decimals, reduced_cost_decimals, solution_decimals) = (decimals(&t.b), decimals(&t.reduced_cost), decimals(&t.solution));
+        let alternative_solution_decimals = if decimal { t.alternative_solutions.iter().map(|solution| decimals(solution)).collect() } else { Vec::new() };
+        let obj_decimal = if decimal { t.obj.to_decimal() } else { String::new() };
+        let objective_value = t.objective_value();
+        let tolerance = |value: Ratio<i64>| if t.arithmetic.as_str() == "float" { Some(value.to_f64()) } else { None };
+        let (feasibility_tolerance, optimality_tolerance, pivot_tolerance) = (tolerance(t.feasibility_tolerance), tolerance(t.optimality_tolerance), tolerance(t.pivot_tolerance));
+        let a_num = t.A.iter().map(|row| row.iter().map(|el| *el.numer()).collect()).collect();
+        let a_den = t.A.iter().map(|row| row.iter().map(|el| *el.denom()).collect()).collect();
+        let (b, c, reduced_cost, solution) = (Parts::new(&t.b), Parts::new(&t.c), Parts::new(&t.reduced_cost), Parts::new(&t.solution));
+        let alternatives: Vec<Parts> = t.alternative_solutions.iter().map(|solution| Parts::new(solution)).collect();
+        let alternative = |part: fn(&Parts) -> &Vec<i64>| alternatives.iter().map(|parts| part(parts).clone()).collect();
         TableauData {
+            A: Vec::new(),
+            b: Vec::new(),
+            c: Vec::new(),
             A_numerators: a_num,
             A_denominators: a_den,
-            b_numerators: b_num,
-            b_denominators: b_den,
-            b_m_numerators: b_m_num,
-            b_m_denominators: b_m_den,
-            c_numerators: c_num,
-            c_denominators: c_den,
-            c_m_numerators: c_m_num,
-            c_m_denominators: c_m_den,
+            b_numerators: b.numerators,
+            b_denominators: b.denominators,
+            b_m_numerators: b.m_numerators,
+            b_m_denominators: b.m_denominators,
+            c_numerators: c.numerators,
+            c_denominators: c.denominators,
+            c_m_numerators: c.m_numerators,
+            c_m_denominators: c.m_denominators,
+            c_values,
             m: t.m,
             n: t.n,
             solve_algorithm: String::from(""),
             variable_select_type: String::from(""),
+            entering_rule: String::from(""),
+            leaving_rule: String::from(""),
             big_M_solve_algorithm: String::from(""),
+            m_value: *t.m_value.numer(),
+            variable_names: t.variable_names,
+            constraint_names: t.constraint_names,
+            decision_variables: t.decision_variables,
+            minimize: t.minimize,
+            free_variables: t.free_variables,
+            arithmetic: t.arithmetic,
+            feasibility_tolerance,
+            optimality_tolerance,
+            pivot_tolerance,
             error: t.error,
             error_message: t.error_message,
-            reduced_cost_numerators: reduced_cost_num,
-            reduced_cost_denominators: reduced_cost_den,
-            reduced_cost_m_numerators: reduced_cost_m_num,
-            reduced_cost_m_denominators: reduced_cost_m_den,
-            obj_numerator: *t.obj.constant.numer(),
-            obj_denominator: *t.obj.constant.denom(),
-            obj_m_numerator: *t.obj.M.numer(),
-            obj_m_denominator: *t.obj.M.denom(),
+            reduced_cost_numerators: reduced_cost.numerators,
+            reduced_cost_denominators: reduced_cost.denominators,
+            reduced_cost_m_numerators: reduced_cost.m_numerators,
+            reduced_cost_m_denominators: reduced_cost.m_denominators,
+            obj_numerator: *t.obj.constant().numer(),
+            obj_denominator: *t.obj.constant().denom(),
+            obj_m_numerator: *t.obj.m().numer(),
+            obj_m_denominator: *t.obj.m().denom(),
             basis_indecies: t.basis_indecies,
             solved: t.solved,
-            solution_numerators: sol_num,
-            solution_denominators: sol_den,
-            solution_m_numerators: sol_m_num,
-            solution_m_denominators: sol_m_den,
+            solution_numerators: solution.numerators,
+            solution_denominators: solution.denominators,
+            solution_m_numerators: solution.m_numerators,
+            solution_m_denominators: solution.m_denominators,
+            explanations: t.steps.into_iter().filter_map(|step| step.explanation).collect(),
+            alternative_solution_numerators: alternative(|parts| &parts.numerators),
+            alternative_solution_denominators: alternative(|parts| &parts.denominators),
+            alternative_solution_m_numerators: alternative(|parts| &parts.m_numerators),
+            alternative_solution_m_denominators: alternative(|parts| &parts.m_denominators),
+            optimal_face: t.optimal_face,
+            unbounded_certificate: t.unbounded_certificate.map(UnboundedCertificateData::new),
+            infeasibility_certificate: t.infeasibility_certificate.map(InfeasibilityCertificateData::new),
+            infeasible_rows: t.infeasible_rows,
+            degenerate_pivot_count: degenerate_steps.len(),
+            degenerate_steps,
+            degenerate_optimal,
+            b_values,
+            reduced_cost_values,
+            obj_value: t.obj,
+            objective_value,
+            solution_values,
+            b_decimals,
+            reduced_cost_decimals,
+            obj_decimal,
+            solution_decimals,
+            alternative_solution_decimals,
+        }
+    }
+
+    // A problem already in the form the tableau expects: maximize cx subject to Ax = b and x >= 0. The solve settings
+    // start at the defaults the web app sends and can be changed before solving.
+    pub fn from_standard_form(a: Vec<Vec<Ratio<i64>>>, b: Vec<Ratio<i64>>, c: Vec<Ratio<i64>>) -> TableauData {
+        TableauData {
+            m: b.len(),
+            n: c.len(),
+            A_numerators: a.iter().map(|row| row.iter().map(|el| *el.numer()).collect()).collect(),
+            A_denominators: a.iter().map(|row| row.iter().map(|el| *el.denom()).collect()).collect(),
+            b_numerators: b.iter().map(|el| *el.numer()).collect(),
+            b_denominators: b.iter().map(|el| *el.denom()).collect(),
+            b_m_numerators: vec![0; b.len()],
+            b_m_denominators: vec![1; b.len()],
+            c_numerators: c.iter().map(|el| *el.numer()).collect(),
+            c_denominators: c.iter().map(|el| *el.denom()).collect(),
+            c_m_numerators: vec![0; c.len()],
+            c_m_denominators: vec![1; c.len()],
+            solve_algorithm: String::from("standard"),
+            variable_select_type: String::from("standard"),
+            big_M_solve_algorithm: String::from("two-phase"),
+            ..Default::default()
+        }
+    }
+
+    pub fn error(error_message: String) -> TableauData {
+        TableauData {
+            error: true,
+            error_message,
+            ..Default::default()
+        }
+    }
+
+    // Values can also be sent as strings in A, b and c, such as "3/4", "-2", "0.125", "1.5e2" or "2M+3" for a cost.
+    // They are read into the numerator and denominator arrays so the rest of the solver only sees one form.
+    pub fn read_strings(&mut self) -> Result<(), String> {
+        let a = std::mem::take(&mut self.A);
+        let b = std::mem::take(&mut self.b);
+        let c = std::mem::take(&mut self.c);
+        if !a.is_empty() {
+            if a.len() != self.m || a.iter().any(|row| row.len() != self.n) {
+                return Err(format!("A needs {} rows of {} values.", self.m, self.n));
+            }
+            let a = a.iter()
+                     .map(|row| row.iter().map(|el| parse_ratio(el)).collect::<Result<Vec<Ratio<i64>>, String>>())
+                     .collect::<Result<Vec<Vec<Ratio<i64>>>, String>>()?;
+            self.A_numerators = a.iter().map(|row| row.iter().map(|el| *el.numer()).collect()).collect();
+            self.A_denominators = a.iter().map(|row| row.iter().map(|el| *el.denom()).collect()).collect();
+        }
+        if !b.is_empty() {
+            if b.len() != self.m {
+                return Err(format!("b needs {} values.", self.m));
+            }
+            let b = b.iter().map(|el| parse_ratio(el)).collect::<Result<Vec<Ratio<i64>>, String>>()?;
+            self.b_numerators = b.iter().map(|el| *el.numer()).collect();
+            self.b_denominators = b.iter().map(|el| *el.denom()).collect();
+            self.b_m_numerators = vec![0; self.m];
+            self.b_m_denominators = vec![1; self.m];
+        }
+        if !c.is_empty() {
+            if c.len() != self.n {
+                return Err(format!("c needs {} values.", self.n));
+            }
+            let c = c.iter().map(|el| parse_m(el)).collect::<Result<Vec<M>, String>>()?;
+            self.c_numerators = c.iter().map(|el| *el.constant().numer()).collect();
+            self.c_denominators = c.iter().map(|el| *el.constant().denom()).collect();
+            self.c_m_numerators = c.iter().map(|el| *el.m().numer()).collect();
+            self.c_m_denominators = c.iter().map(|el| *el.m().denom()).collect();
+            self.c_values = c;
         }
+        Ok(())
     }
 }
@@ -0,0 +1,40 @@
+use crate::tableau_data::TableauData;
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Deserialize)]
+pub struct VerificationRequest {
+    pub previous: TableauData,
+    pub next: TableauData,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CellMistake {
+    pub location: String,
+    pub row: Option<usize>,
+    pub col: Option<usize>,
+    pub expected: String,
+    pub submitted: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VerificationData {
+    pub entering_index: Option<usize>,
+    pub leaving_index: Option<usize>,
+    pub correct: bool,
+    pub mistakes: Vec<CellMistake>,
+    pub error: bool,
+    pub error_message: String,
+}
+
+impl VerificationData {
+    pub fn error(error_message: String) -> VerificationData {
+        VerificationData {
+            entering_index: None,
+            leaving_index: None,
+            correct: false,
+            mistakes: Vec::new(),
+            error: true,
+            error_message,
+        }
+    }
+}
\ No newline at end of file
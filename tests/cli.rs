@@ -0,0 +1,47 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn simplex(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_simplex")).args(args)
+                                                             .stdin(Stdio::piped())
+                                                             .stdout(Stdio::piped())
+                                                             .stderr(Stdio::piped())
+                                                             .spawn()
+                                                             .unwrap();
+    // The binary may exit on a bad option before reading its input, so a closed pipe is not an error here.
+    let _ = child.stdin.take().unwrap().write_all(input.as_bytes());
+    child.wait_with_output().unwrap()
+}
+
+fn lp(objective: &str, constraints: &[&str]) -> String {
+    format!("Maximize\n obj: {}\nSubject To\n {}\nEnd\n", objective, constraints.join("\n "))
+}
+
+#[test]
+fn exits_0_with_the_trace_of_a_solved_problem() {
+    let output = simplex(&["--input", "lp"], &lp("3 x + 2 y", &["c1: x + y <= 4", "c2: x + 3 y <= 6"]));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(output.status.code(), Some(0), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("Solution\n") && stdout.contains("z = 12"), "{}", stdout);
+}
+
+#[test]
+fn exits_2_when_the_problem_has_no_optimum() {
+    let infeasible = simplex(&["--input", "lp"], &lp("x + y", &["c1: x + y <= 1", "c2: x + y >= 3"]));
+    assert_eq!(infeasible.status.code(), Some(2));
+    assert!(String::from_utf8(infeasible.stdout).unwrap().contains("infeasible"));
+    let unbounded = simplex(&["--input", "lp", "--output", "json"], &lp("x + y", &["c1: x - y <= 1"]));
+    assert_eq!(unbounded.status.code(), Some(2));
+    assert!(String::from_utf8(unbounded.stdout).unwrap().contains("unbounded"));
+}
+
+#[test]
+fn exits_1_for_unreadable_problems_and_unsupported_options() {
+    let problem = lp("3 x + 2 y", &["c1: x + y <= 4"]);
+    for args in [&["--input", "lp", "--entering", "nonsense"][..], &["--input", "lp", "--output", "pdf"], &["--input", "yaml"], &["--color"]] {
+        let output = simplex(args, &problem);
+        assert_eq!(output.status.code(), Some(1), "{:?}", args);
+        assert!(String::from_utf8(output.stderr).unwrap().starts_with("simplex: "), "{:?}", args);
+    }
+    assert_eq!(simplex(&[], "{").status.code(), Some(1));
+}